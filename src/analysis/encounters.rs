@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ddragon::static_data::StaticData;
+use crate::models::lol_match::MatchData;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Encounter {
+    pub match_id: String,
+    pub game_creation: i64,
+    pub game_version: String,
+    pub same_team: bool,
+    pub champion_id: i64,
+    pub champion_name: String,
+    pub item_ids: Vec<i64>,
+    /// Names of `item_ids`, empty until resolved with the static data of the match's patch.
+    pub items: Vec<String>,
+    /// Whether the tracked summoner won this game.
+    pub won: bool,
+}
+
+impl Encounter {
+    /// Resolves champion and item names, `static_data` should be for the patch the match was
+    /// played on as items may since have been removed.
+    pub fn resolve_names(&mut self, static_data: &StaticData) {
+        self.champion_name = static_data.champion_name(self.champion_id);
+        self.items = self
+            .item_ids
+            .iter()
+            .map(|id| {
+                static_data
+                    .item(*id)
+                    .map(|i| i.name.to_owned())
+                    .unwrap_or_else(|| format!("Item {}", id))
+            })
+            .collect();
+    }
+}
+
+/// Finds previous games in `history` that the tracked summoner played with or against `puuid`,
+/// most recent first.
+pub fn find_encounters<'a>(
+    my_puuid: &str,
+    puuid: &str,
+    history: impl Iterator<Item = &'a MatchData>,
+) -> Vec<Encounter> {
+    let mut encounters = history
+        .filter_map(|match_data| {
+            let participants = &match_data.info.participants;
+
+            let me = participants.iter().find(|p| p.puuid == my_puuid)?;
+            let them = participants.iter().find(|p| p.puuid == puuid)?;
+
+            Some(Encounter {
+                match_id: match_data.metadata.match_id.clone(),
+                game_creation: match_data.info.game_creation,
+                game_version: match_data.info.game_version.clone(),
+                same_team: me.team_id == them.team_id,
+                champion_id: them.champion_id,
+                champion_name: them.champion_name.clone(),
+                item_ids: [
+                    them.item0, them.item1, them.item2, them.item3, them.item4, them.item5,
+                    them.item6,
+                ]
+                .into_iter()
+                .filter(|id| *id > 0)
+                .collect(),
+                items: Vec::new(),
+                won: me.win,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    encounters.sort_by_key(|e| std::cmp::Reverse(e.game_creation));
+
+    encounters
+}
+
+pub fn encounters_string(encounters: &[Encounter]) -> String {
+    if encounters.is_empty() {
+        return String::new();
+    }
+
+    let summary = |same_team: bool| {
+        let games = encounters
+            .iter()
+            .filter(|e| e.same_team == same_team)
+            .collect::<Vec<_>>();
+
+        let wins = games.iter().filter(|e| e.won).count();
+
+        (games.len(), wins)
+    };
+
+    let (with_games, with_wins) = summary(true);
+    let (against_games, against_wins) = summary(false);
+
+    let mut parts = Vec::with_capacity(2);
+
+    if with_games > 0 {
+        parts.push(format!("With: {}W {}L", with_wins, with_games - with_wins));
+    }
+
+    if against_games > 0 {
+        parts.push(format!(
+            "Against: {}W {}L",
+            against_wins,
+            against_games - against_wins
+        ));
+    }
+
+    format!(
+        "{} (last as {})",
+        parts.join(", "),
+        encounters[0].champion_name
+    )
+}
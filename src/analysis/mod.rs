@@ -0,0 +1,4 @@
+pub mod encounters;
+pub mod premade;
+pub mod roles;
+pub mod smurf;
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use crate::models::lol_match::MatchData;
+
+/// Minimum number of recent games two players must have played on the same team
+/// before we consider them to be queued together.
+const MIN_SHARED_GAMES: usize = 2;
+
+#[derive(Debug)]
+pub struct PremadePlayer<'a> {
+    pub puuid: &'a str,
+    pub team_id: u8,
+}
+
+/// Groups players of the current game that have recently played together on the same team.
+///
+/// Returns a map of puuid to a group number, starting at 1. Players that don't appear to be
+/// queued with anyone are not included.
+pub fn detect_premades(
+    players: &[PremadePlayer<'_>],
+    recent_matches: &HashMap<String, Vec<MatchData>>,
+) -> HashMap<String, u8> {
+    let mut unique_matches = HashMap::new();
+
+    for match_data in recent_matches.values().flatten() {
+        unique_matches.insert(match_data.metadata.match_id.as_str(), match_data);
+    }
+
+    let mut shared_games = vec![vec![0; players.len()]; players.len()];
+
+    for match_data in unique_matches.values() {
+        let match_teams = players
+            .iter()
+            .map(|player| {
+                match_data
+                    .info
+                    .participants
+                    .iter()
+                    .find(|p| p.puuid == player.puuid)
+                    .map(|p| p.team_id)
+            })
+            .collect::<Vec<_>>();
+
+        for (i, player) in players.iter().enumerate() {
+            for j in (i + 1)..players.len() {
+                if player.team_id != players[j].team_id {
+                    continue;
+                }
+
+                if let (Some(a), Some(b)) = (match_teams[i], match_teams[j]) {
+                    if a == b {
+                        shared_games[i][j] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut parents = (0..players.len()).collect::<Vec<_>>();
+
+    for (i, row) in shared_games.iter().enumerate() {
+        for (j, &games) in row.iter().enumerate().skip(i + 1) {
+            if games >= MIN_SHARED_GAMES {
+                let root_i = find_root(&mut parents, i);
+                let root_j = find_root(&mut parents, j);
+
+                parents[root_j] = root_i;
+            }
+        }
+    }
+
+    let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for i in 0..players.len() {
+        let root = find_root(&mut parents, i);
+
+        members.entry(root).or_default().push(i);
+    }
+
+    let mut groups = members
+        .into_values()
+        .filter(|m| m.len() > 1)
+        .collect::<Vec<_>>();
+
+    groups.sort();
+
+    groups
+        .into_iter()
+        .enumerate()
+        .flat_map(|(group, m)| {
+            m.into_iter()
+                .map(move |i| (players[i].puuid.to_owned(), group as u8 + 1))
+        })
+        .collect()
+}
+
+fn find_root(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    parents[i] = root;
+
+    root
+}
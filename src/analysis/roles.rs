@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
+
+use crate::models::ddragon_champions::Champion;
+use crate::models::lol_match::MatchData;
+
+const SMITE_SPELL_ID: i64 = 11;
+const EXHAUST_SPELL_ID: i64 = 3;
+const HEAL_SPELL_ID: i64 = 7;
+const TELEPORT_SPELL_ID: i64 = 12;
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum Role {
+    Top,
+    Jungle,
+    Middle,
+    Bottom,
+    Utility,
+}
+
+impl Role {
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Role::Top => "Top",
+            Role::Jungle => "Jungle",
+            Role::Middle => "Mid",
+            Role::Bottom => "Bot",
+            Role::Utility => "Support",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleShare {
+    pub role: Role,
+    pub games: u8,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RoleInfo {
+    /// Roles played in recent matches, most played first.
+    pub distribution: Vec<RoleShare>,
+    pub main_role: Option<Role>,
+    pub predicted_role: Option<Role>,
+    /// Whether the predicted role for the current game differs from the main role.
+    pub off_role: bool,
+}
+
+impl RoleInfo {
+    pub fn new(
+        puuid: &str,
+        champion: Option<&Champion>,
+        spells: (i64, i64),
+        matches: &[MatchData],
+    ) -> Self {
+        let distribution = role_distribution(puuid, matches);
+        let main_role = distribution.first().map(|r| r.role);
+        let predicted_role = predict_role(puuid, champion, spells, matches);
+
+        let off_role = matches!((main_role, predicted_role), (Some(main), Some(predicted)) if main != predicted);
+
+        RoleInfo {
+            distribution,
+            main_role,
+            predicted_role,
+            off_role,
+        }
+    }
+
+    pub fn role_string(&self) -> String {
+        match (self.predicted_role, self.main_role) {
+            (Some(predicted), Some(main)) if self.off_role => {
+                format!(
+                    "{} (Main: {}, Autofill?)",
+                    predicted.short_name(),
+                    main.short_name()
+                )
+            }
+            (Some(predicted), _) => predicted.short_name().to_owned(),
+            (None, Some(main)) => format!("? (Main: {})", main.short_name()),
+            (None, None) => "Unknown".to_owned(),
+        }
+    }
+}
+
+fn position(puuid: &str, match_data: &MatchData) -> Option<(i64, Role)> {
+    match_data
+        .info
+        .participants
+        .iter()
+        .find(|p| p.puuid == puuid)
+        .and_then(|p| Some((p.champion_id, p.team_position.parse().ok()?)))
+}
+
+/// Roles played in `matches`, most played first, ties in map order (top to support).
+pub fn role_distribution(puuid: &str, matches: &[MatchData]) -> Vec<RoleShare> {
+    let mut counts: HashMap<Role, u8> = HashMap::new();
+
+    for (_, role) in matches.iter().filter_map(|m| position(puuid, m)) {
+        *counts.entry(role).or_default() += 1;
+    }
+
+    let mut distribution = counts
+        .into_iter()
+        .map(|(role, games)| RoleShare { role, games })
+        .collect::<Vec<_>>();
+
+    distribution.sort_by(|a, b| b.games.cmp(&a.games).then(a.role.cmp(&b.role)));
+
+    distribution
+}
+
+/// Predicts the role played in the current game from the summoner spells taken, the role this
+/// player usually plays the champion in, and finally the champion's class.
+pub fn predict_role(
+    puuid: &str,
+    champion: Option<&Champion>,
+    spells: (i64, i64),
+    matches: &[MatchData],
+) -> Option<Role> {
+    let has_spell = |id: i64| spells.0 == id || spells.1 == id;
+
+    if has_spell(SMITE_SPELL_ID) {
+        return Some(Role::Jungle);
+    }
+
+    let champion = champion?;
+
+    let champion_roles = matches
+        .iter()
+        .filter_map(|m| position(puuid, m))
+        .filter(|(champion_id, role)| *champion_id == champion.key && *role != Role::Jungle)
+        .map(|(_, role)| role)
+        .collect::<Vec<_>>();
+
+    if let Some(role) = most_common(&champion_roles) {
+        return Some(role);
+    }
+
+    let has_tag = |tag: &str| champion.tags.iter().any(|t| t == tag);
+    let primary_tag = champion.tags.first().map(|t| t.as_str());
+
+    // Spells matching one of the champion's classes say more than its primary class alone.
+    let role = match primary_tag {
+        _ if has_spell(HEAL_SPELL_ID) && has_tag("Marksman") => Role::Bottom,
+        _ if has_spell(EXHAUST_SPELL_ID) && has_tag("Support") => Role::Utility,
+        _ if has_spell(TELEPORT_SPELL_ID) && (has_tag("Fighter") || has_tag("Tank")) => Role::Top,
+        Some("Support") => Role::Utility,
+        Some("Marksman") => Role::Bottom,
+        Some("Fighter") | Some("Tank") => Role::Top,
+        _ => Role::Middle,
+    };
+
+    Some(role)
+}
+
+/// Most frequent of `roles`, ties going to the role first in map order.
+fn most_common(roles: &[Role]) -> Option<Role> {
+    let mut counts: HashMap<Role, u8> = HashMap::new();
+
+    for role in roles.iter() {
+        *counts.entry(*role).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .max_by(|(role_a, count_a), (role_b, count_b)| {
+            count_a.cmp(count_b).then(role_b.cmp(role_a))
+        })
+        .map(|(role, _)| role)
+}
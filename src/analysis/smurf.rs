@@ -0,0 +1,198 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+use crate::models::leagues::LeagueRank;
+use crate::models::lol_match::MatchData;
+use crate::Result;
+
+const TIERS: [&str; 10] = [
+    "IRON",
+    "BRONZE",
+    "SILVER",
+    "GOLD",
+    "PLATINUM",
+    "EMERALD",
+    "DIAMOND",
+    "MASTER",
+    "GRANDMASTER",
+    "CHALLENGER",
+];
+
+/// Weights and limits of the smurf score, loaded from a JSON file with `from_file`. Fields missing
+/// from the file keep their default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmurfConfig {
+    /// Score (0-100) at which a player is flagged.
+    pub threshold: u8,
+    /// Summoner level below which an account is considered new.
+    pub max_level: i64,
+    pub low_level_weight: u8,
+    /// Ranked games below which an account is considered to have few games.
+    pub min_ranked_games: i64,
+    pub few_games_weight: u8,
+    /// Ranked win rate (percent) considered suspiciously high.
+    pub high_win_rate: u8,
+    /// Ranked games needed before the win rate is taken into account.
+    pub win_rate_min_games: i64,
+    pub win_rate_weight: u8,
+    /// Average KDA over recent matches considered suspiciously high.
+    pub high_kda: f32,
+    pub kda_weight: u8,
+    /// Lowest tier considered high for a new account.
+    pub high_tier: String,
+    pub rank_for_age_weight: u8,
+}
+
+impl Default for SmurfConfig {
+    fn default() -> Self {
+        SmurfConfig {
+            threshold: 50,
+            max_level: 60,
+            low_level_weight: 25,
+            min_ranked_games: 50,
+            few_games_weight: 15,
+            high_win_rate: 60,
+            win_rate_min_games: 10,
+            win_rate_weight: 25,
+            high_kda: 4.0,
+            kda_weight: 20,
+            high_tier: "PLATINUM".to_owned(),
+            rank_for_age_weight: 15,
+        }
+    }
+}
+
+impl SmurfConfig {
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let config: SmurfConfig = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.threshold > 100 {
+            return Err(anyhow!("Smurf threshold must be between 0 and 100").into());
+        }
+
+        if self.high_win_rate > 100 {
+            return Err(anyhow!("Smurf high win rate must be between 0 and 100").into());
+        }
+
+        if tier_index(&self.high_tier).is_none() {
+            return Err(anyhow!("Unknown tier: {}", self.high_tier).into());
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SmurfScore {
+    pub score: u8,
+    pub flagged: bool,
+    pub factors: Vec<String>,
+}
+
+impl SmurfScore {
+    pub fn new(
+        config: &SmurfConfig,
+        summoner_level: i64,
+        rank: Option<&LeagueRank>,
+        puuid: &str,
+        matches: &[MatchData],
+    ) -> Self {
+        let mut score = 0u16;
+        let mut factors = Vec::new();
+
+        let new_account = summoner_level < config.max_level;
+
+        if new_account {
+            score += config.low_level_weight as u16;
+            factors.push(format!("Low level ({})", summoner_level));
+        }
+
+        if let Some(rank) = rank {
+            let ranked_games = rank.wins + rank.losses;
+
+            if ranked_games < config.min_ranked_games {
+                score += config.few_games_weight as u16;
+                factors.push(format!("Few ranked games ({})", ranked_games));
+            }
+
+            if ranked_games >= config.win_rate_min_games {
+                let win_rate = rank.wins * 100 / ranked_games;
+
+                if win_rate >= config.high_win_rate as i64 {
+                    score += config.win_rate_weight as u16;
+                    factors.push(format!("High win rate ({}%)", win_rate));
+                }
+            }
+
+            let high_rank = match (tier_index(&rank.tier), tier_index(&config.high_tier)) {
+                (Some(tier), Some(high_tier)) => tier >= high_tier,
+                _ => false,
+            };
+
+            if new_account && high_rank {
+                score += config.rank_for_age_weight as u16;
+                factors.push(format!(
+                    "High rank for account age ({} at level {})",
+                    rank.tier, summoner_level
+                ));
+            }
+        }
+
+        if let Some(kda) = average_kda(puuid, matches) {
+            if kda >= config.high_kda {
+                score += config.kda_weight as u16;
+                factors.push(format!("High KDA ({:.1})", kda));
+            }
+        }
+
+        let score = score.min(100) as u8;
+
+        SmurfScore {
+            score,
+            flagged: score >= config.threshold,
+            factors,
+        }
+    }
+}
+
+pub fn tier_index(tier: &str) -> Option<usize> {
+    TIERS.iter().position(|t| t.eq_ignore_ascii_case(tier))
+}
+
+/// Position of a rank on the ladder in LP, counting 100 LP per division, so that ranks in
+/// different divisions can be compared and subtracted.
+pub fn ladder_points(tier: &str, division: &str, league_points: i64) -> Option<i64> {
+    let division = match division {
+        "IV" => 0,
+        "III" => 1,
+        "II" => 2,
+        _ => 3,
+    };
+
+    Some((tier_index(tier)? as i64 * 4 + division) * 100 + league_points)
+}
+
+pub fn average_kda(puuid: &str, matches: &[MatchData]) -> Option<f32> {
+    let (kills_assists, deaths, games) = matches
+        .iter()
+        .filter_map(|m| m.info.participants.iter().find(|p| p.puuid == puuid))
+        .fold((0, 0, 0), |(ka, d, g), p| {
+            (ka + p.kills + p.assists, d + p.deaths, g + 1)
+        });
+
+    if games == 0 {
+        return None;
+    }
+
+    Some(kills_assists as f32 / deaths.max(1) as f32)
+}
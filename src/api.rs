@@ -1,50 +1,127 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
 use reqwest::header::{HeaderMap, HeaderValue};
 use reqwest::{Client, ClientBuilder, StatusCode};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 use strum_macros::{Display, EnumString, EnumVariantNames};
+use tracing::{field, Instrument, Span};
 
-use crate::ddragon::updater::DDragonUpdater;
+use crate::ddragon::static_data::StaticData;
+use crate::ddragon::updater::{ddragon_version, DDragonUpdater};
+use crate::endpoints::champion_mastery::ChampionMasteryEndpoint;
 use crate::endpoints::leagues::LeagueRankEndpoint;
 use crate::endpoints::lol_match::MatchEndpoint;
 use crate::endpoints::spectator::SpectatorEndpoint;
 use crate::endpoints::summoner::SummonerEndpointBy;
 use crate::endpoints::Endpoint;
-use crate::models::ddragon_champions::ChampionData;
+use crate::metrics;
 use crate::models::error::MyError;
 use crate::models::summoner::{Summoner, SummonerInfo};
+use crate::response_cache::{CacheStats, ResponseCache};
+use crate::retry::{self, RetryPolicy};
 use crate::Result;
 
-pub struct Api<'a> {
-    key: &'a str,
+/// Minimum time between attempts to pick up a new static data version mid-session.
+const STATIC_DATA_REFRESH_INTERVAL: Duration = Duration::from_secs(600);
+
+/// Handle to the Riot API, cheap to clone and share between tasks.
+#[derive(Clone)]
+pub struct Api {
+    inner: Arc<ApiInner>,
+    priority: Priority,
+}
+
+/// Priority of the requests made through an `Api` handle. When the rate limit budget runs low,
+/// lower priority requests wait until no higher priority requests are queued.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Syncing data nobody is waiting on, such as the tracked summoner's match history.
+    Background,
+    /// Detecting and loading the current game.
+    LiveGame,
+    /// Requests made directly on behalf of the user.
+    Interactive,
+}
+
+impl Priority {
+    const COUNT: usize = 3;
+
+    /// Share of every rate limit bucket that requests of this priority leave for higher priority
+    /// requests.
+    fn reserved_share(&self) -> f32 {
+        match self {
+            Priority::Background => 0.3,
+            Priority::LiveGame => 0.1,
+            Priority::Interactive => 0.0,
+        }
+    }
+}
+
+struct ApiInner {
+    key: String,
     client: Client,
     root_endpoint: String,
     v5_root_endpoint: String,
     region: ApiRegion,
-    limiters: Vec<Limiter>,
-    pub champion_data: ChampionData,
+    scheduler: Scheduler,
+    response_cache: ResponseCache,
+    requests_sent: AtomicU64,
+    locale: String,
+    retry_policy: RetryPolicy,
+    static_data: RwLock<Arc<StaticData>>,
+    /// Static data of older patches, loaded on demand for rendering older matches.
+    patch_static_data: RwLock<HashMap<String, Arc<StaticData>>>,
+    /// `None` until the first refresh, so a patch released before startup is picked up at once.
+    last_static_data_refresh: Mutex<Option<Instant>>,
 }
 
-impl fmt::Debug for Api<'_> {
+impl fmt::Debug for Api {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Key: {} - Region: {}", self.key, self.region)
+        write!(
+            f,
+            "Key: {} - Region: {} - Priority: {:?}",
+            redact_key(&self.inner.key),
+            self.inner.region,
+            self.priority
+        )
     }
 }
 
-impl fmt::Display for Api<'_> {
+impl fmt::Display for Api {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Key: {} - Region: {}", self.key, self.region)
+        write!(
+            f,
+            "Key: {} - Region: {}",
+            redact_key(&self.inner.key),
+            self.inner.region
+        )
+    }
+}
+
+/// Only the last characters of the key are shown, enough to tell keys apart in logs.
+pub fn redact_key(key: &str) -> String {
+    let visible = key.len().saturating_sub(4);
+
+    match key.get(visible..) {
+        Some(end) if visible > 0 => format!("***{}", end),
+        _ => "***".to_owned(),
     }
 }
 
-impl<'a> Api<'a> {
-    pub async fn new(key: &'a str, region: ApiRegion) -> Result<Api<'a>> {
+impl Api {
+    pub async fn new(
+        key: &str,
+        region: ApiRegion,
+        locale: &str,
+        retry_policy: RetryPolicy,
+    ) -> Result<Api> {
         let mut default_headers = HeaderMap::new();
 
         default_headers.insert(
@@ -64,126 +141,439 @@ impl<'a> Api<'a> {
 
         let limiters = vec![l1, l2];
 
-        let ddragon = DDragonUpdater::new().await?;
-        let champion_data = ddragon.download_latest_champions().await?;
+        let ddragon = DDragonUpdater::new(locale, &retry_policy).await?;
+        let static_data = ddragon.download_static_data().await?;
+
+        info!("Loaded static data for patch {}", static_data.version);
 
         Ok(Self {
-            key,
-            client,
-            root_endpoint: region.get_root_endpoint(),
-            v5_root_endpoint: region.get_v5_root_endpoint(),
-            region,
-            limiters,
-            champion_data,
+            inner: Arc::new(ApiInner {
+                key: key.to_owned(),
+                client,
+                root_endpoint: region.get_root_endpoint(),
+                v5_root_endpoint: region.get_v5_root_endpoint(),
+                region,
+                scheduler: Scheduler::new(limiters),
+                response_cache: ResponseCache::default(),
+                requests_sent: AtomicU64::new(0),
+                locale: locale.to_owned(),
+                retry_policy,
+                static_data: RwLock::new(Arc::new(static_data)),
+                patch_static_data: RwLock::new(HashMap::new()),
+                last_static_data_refresh: Mutex::new(None),
+            }),
+            priority: Priority::Interactive,
         })
     }
 
-    pub async fn get_summoner(&self, endpoint: SummonerEndpointBy<'_>) -> Result<Summoner<'_>> {
-        let res = self.call_endpoint(endpoint, false).await?;
-        let summoner_info = serde_json::from_str::<SummonerInfo>(&res)?;
+    /// A handle to the same API whose requests are scheduled with `priority`.
+    pub fn with_priority(&self, priority: Priority) -> Api {
+        Api {
+            inner: Arc::clone(&self.inner),
+            priority,
+        }
+    }
+
+    pub fn priority(&self) -> Priority {
+        self.priority
+    }
+
+    pub fn region(&self) -> &ApiRegion {
+        &self.inner.region
+    }
+
+    /// Requests left in each rate limit window.
+    pub fn rate_limit_budget(&self) -> Vec<RateLimitBudget> {
+        self.inner.scheduler.budget()
+    }
 
-        Ok(Summoner::new(summoner_info, self))
+    pub fn cache_stats(&self) -> CacheStats {
+        self.inner.response_cache.stats()
+    }
+
+    pub fn response_cache(&self) -> &ResponseCache {
+        &self.inner.response_cache
+    }
+
+    /// Requests sent to the Riot API by all handles, including retries.
+    pub fn requests_sent(&self) -> u64 {
+        self.inner.requests_sent.load(Ordering::Relaxed)
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.inner.retry_policy
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.inner.locale
+    }
+
+    pub fn static_data(&self) -> Arc<StaticData> {
+        Arc::clone(
+            &self
+                .inner
+                .static_data
+                .read()
+                .expect("Static data lock poisoned"),
+        )
+    }
+
+    /// Static data of the patch a match was played on, falling back to the current static data
+    /// if that patch can't be loaded.
+    pub async fn static_data_for_game_version(&self, game_version: &str) -> Arc<StaticData> {
+        let current = self.static_data();
+
+        let version = match ddragon_version(game_version) {
+            Some(version) if version != current.version => version,
+            _ => return current,
+        };
+
+        if let Some(static_data) = self
+            .inner
+            .patch_static_data
+            .read()
+            .expect("Patch static data lock poisoned")
+            .get(&version)
+        {
+            return Arc::clone(static_data);
+        }
+
+        let static_data =
+            DDragonUpdater::with_version(&version, &self.inner.locale, &self.inner.retry_policy)
+                .download_static_data()
+                .await;
+
+        // Failed patches also fall back to the current static data for the rest of the session,
+        // so that we don't retry the download for every match of that patch.
+        let static_data = match static_data {
+            Ok(static_data) => {
+                info!("Loaded static data for patch {}", version);
+
+                Arc::new(static_data)
+            }
+            Err(e) => {
+                warn!(
+                    "Could not load static data for patch {} - Using {}: {}",
+                    version, current.version, e
+                );
+
+                current
+            }
+        };
+
+        self.inner
+            .patch_static_data
+            .write()
+            .expect("Patch static data lock poisoned")
+            .insert(version, Arc::clone(&static_data));
+
+        static_data
+    }
+
+    /// Reloads static data if Data Dragon has released a newer version, e.g. when a champion
+    /// from a new patch shows up. Attempts are limited to one per `STATIC_DATA_REFRESH_INTERVAL`.
+    pub async fn refresh_static_data(&self) -> Result<()> {
+        {
+            let mut last_refresh = self
+                .inner
+                .last_static_data_refresh
+                .lock()
+                .expect("Static data refresh lock poisoned");
+
+            if matches!(*last_refresh, Some(last) if last.elapsed() < STATIC_DATA_REFRESH_INTERVAL)
+            {
+                return Ok(());
+            }
+
+            *last_refresh = Some(Instant::now());
+        }
+
+        let ddragon = DDragonUpdater::new(&self.inner.locale, &self.inner.retry_policy).await?;
+
+        if ddragon.version() == self.static_data().version {
+            return Ok(());
+        }
+
+        let static_data = ddragon.download_static_data().await?;
+
+        info!("Updated static data to patch {}", static_data.version);
+
+        *self
+            .inner
+            .static_data
+            .write()
+            .expect("Static data lock poisoned") = Arc::new(static_data);
+
+        Ok(())
+    }
+
+    /// Refreshes static data if any of the champions aren't known yet, warning about those still
+    /// unknown afterwards.
+    pub async fn ensure_champions_known(&self, champion_ids: impl Iterator<Item = i64>) {
+        let static_data = self.static_data();
+
+        let unknown = champion_ids
+            .into_iter()
+            .filter(|id| static_data.champion(*id).is_err())
+            .collect::<Vec<_>>();
+
+        if unknown.is_empty() {
+            return;
+        }
+
+        info!(
+            "Champion {} is unknown, checking for a static data update...",
+            unknown[0]
+        );
+
+        if let Err(e) = self.refresh_static_data().await {
+            error!("Could not refresh static data: {}", e);
+        }
+
+        let static_data = self.static_data();
+
+        for champion_id in unknown {
+            if let Err(e) = static_data.champion(champion_id) {
+                warn!("{}", e);
+            }
+        }
+    }
+
+    pub async fn get_summoner(&self, endpoint: SummonerEndpointBy<'_>) -> Result<Summoner> {
+        let summoner_info = self.get::<SummonerInfo>(endpoint, false).await?;
+
+        Ok(Summoner::new(summoner_info, self.clone()))
     }
 
     pub async fn get_spectator<T: DeserializeOwned>(
         &self,
         endpoint: SpectatorEndpoint<'_>,
     ) -> Result<T> {
-        let res = self.call_endpoint(endpoint, false).await?;
-
-        Ok(serde_json::from_str::<T>(&res)?)
+        self.get(endpoint, false).await
     }
 
     pub async fn get_match<T: DeserializeOwned>(&self, endpoint: MatchEndpoint<'_>) -> Result<T> {
-        let res = self.call_endpoint(endpoint, true).await?;
-
-        Ok(serde_json::from_str::<T>(&res)?)
+        self.get(endpoint, true).await
     }
 
     pub async fn get_leagues<T: DeserializeOwned>(
         &self,
         endpoint: LeagueRankEndpoint<'_>,
     ) -> Result<T> {
-        let res = self.call_endpoint(endpoint, false).await?;
+        self.get(endpoint, false).await
+    }
+
+    /// Like `get_leagues`, but skips the response cache, for ranks that may have just changed.
+    pub async fn get_leagues_fresh<T: DeserializeOwned>(
+        &self,
+        endpoint: LeagueRankEndpoint<'_>,
+    ) -> Result<T> {
+        let cache_ttl = endpoint.cache_ttl();
+        let endpoint_name = endpoint.name();
+        let endpoint_url = endpoint.url();
 
-        Ok(serde_json::from_str::<T>(&res)?)
+        let res = self
+            .inner
+            .response_cache
+            .refresh(&endpoint_url, cache_ttl, || {
+                self.call_endpoint(&endpoint_url, endpoint_name, false)
+            })
+            .await?;
+
+        serde_json::from_str::<T>(&res)
+            .map_err(|source| MyError::deserialize(endpoint_url, &res, source))
+    }
+
+    pub async fn get_champion_mastery<T: DeserializeOwned>(
+        &self,
+        endpoint: ChampionMasteryEndpoint<'_>,
+    ) -> Result<T> {
+        self.get(endpoint, false).await
     }
 
-    async fn call_endpoint(&self, endpoint: impl Endpoint, is_v5: bool) -> Result<String> {
+    async fn get<T: DeserializeOwned>(&self, endpoint: impl Endpoint, is_v5: bool) -> Result<T> {
+        let cache_ttl = endpoint.cache_ttl();
+        let endpoint_name = endpoint.name();
         let endpoint_url = endpoint.url();
 
-        let mut attempts = 0;
+        let res = self
+            .inner
+            .response_cache
+            .get_or_fetch(&endpoint_url, cache_ttl, || {
+                self.call_endpoint(&endpoint_url, endpoint_name, is_v5)
+            })
+            .await?;
 
-        'outer: loop {
-            if attempts == 3 {
-                return Err(MyError::Other(anyhow!(
-                    "Failed to make request: {}",
-                    endpoint_url
-                )));
-            }
+        serde_json::from_str::<T>(&res)
+            .map_err(|source| MyError::deserialize(endpoint_url, &res, source))
+    }
 
-            for l in self.limiters.iter() {
-                // Wait until limiters no longer give us an error.
-                while l.take().is_err() {
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                }
+    async fn call_endpoint(
+        &self,
+        endpoint_url: &str,
+        endpoint_name: &'static str,
+        is_v5: bool,
+    ) -> Result<String> {
+        let mut retry = self.inner.retry_policy.start();
+
+        loop {
+            self.inner.scheduler.wait_for_budget(self.priority).await;
+
+            let span = debug_span!(
+                "request",
+                endpoint = endpoint_name,
+                attempt = retry.attempts() + 1,
+                status = field::Empty,
+                latency_ms = field::Empty,
+            );
+
+            let (error, retry_after) = match self
+                .attempt(endpoint_url, endpoint_name, is_v5)
+                .instrument(span)
+                .await
+            {
+                Attempt::Done(res) => return res,
+                Attempt::Retry(error, retry_after) => (error, retry_after),
+            };
+
+            match retry.next_delay(retry_after) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(error),
             }
+        }
+    }
 
-            let root_endpoint = if !is_v5 {
-                &self.root_endpoint
-            } else {
-                &self.v5_root_endpoint
-            };
+    /// Sends a single request, recording its status and latency on the current span.
+    async fn attempt(
+        &self,
+        endpoint_url: &str,
+        endpoint_name: &'static str,
+        is_v5: bool,
+    ) -> Attempt {
+        let retry_policy = &self.inner.retry_policy;
+
+        let root_endpoint = if !is_v5 {
+            &self.inner.root_endpoint
+        } else {
+            &self.inner.v5_root_endpoint
+        };
 
-            let res = self
-                .client
-                .get(format!("{}/{}", root_endpoint, endpoint_url))
-                .send()
-                .await?;
+        let started = Instant::now();
 
-            attempts += 1;
+        self.inner.requests_sent.fetch_add(1, Ordering::Relaxed);
 
-            match res.status() {
-                StatusCode::TOO_MANY_REQUESTS => {
-                    if let Some(retry_after) = res.headers().get("retry-after") {
-                        let delay = retry_after.to_str().unwrap().parse::<u64>().unwrap();
+        let res = self
+            .inner
+            .client
+            .get(format!("{}/{}", root_endpoint, endpoint_url))
+            .send()
+            .await;
 
-                        // Empty all buckets to stop any requests in other threads from continuing
-                        for l in self.limiters.iter() {
-                            l.empty();
-                        }
+        let span = Span::current();
+        span.record("latency_ms", &(started.elapsed().as_millis() as u64));
 
-                        info!(
-                            "TOO_MANY_REQUESTS received - Delaying for {} seconds.",
-                            delay
-                        );
+        match res {
+            Err(e) => {
+                let retryable = retry_policy.is_retryable_error(&e);
 
-                        for l in self.limiters.iter() {
-                            l.refill();
-                        }
+                debug!(error = %e, "Request failed");
+
+                metrics::global().record_request(
+                    endpoint_name,
+                    if e.is_timeout() { "timeout" } else { "error" },
+                );
 
-                        tokio::time::sleep(Duration::from_secs(delay)).await;
+                let error = if e.is_timeout() {
+                    MyError::Timeout {
+                        endpoint: endpoint_url.to_owned(),
                     }
+                } else {
+                    MyError::Reqwest(e)
+                };
+
+                if !retryable {
+                    return Attempt::Done(Err(error));
                 }
-                StatusCode::NOT_FOUND => {
-                    return Err(MyError::Other(anyhow!(
-                        "No data was found for endpoint: {}",
+
+                Attempt::Retry(error, None)
+            }
+            Ok(res) => {
+                let status = res.status();
+                let retry_after = retry::retry_after(&res);
+
+                span.record("status", &status.as_u16());
+                debug!("Response received");
+
+                metrics::global().record_request(endpoint_name, status.as_str());
+
+                let error = match status {
+                    StatusCode::OK => return Attempt::Done(res.text().await.map_err(Into::into)),
+                    StatusCode::UNAUTHORIZED => MyError::Unauthorized,
+                    StatusCode::FORBIDDEN => MyError::Forbidden {
+                        endpoint: endpoint_url.to_owned(),
+                    },
+                    StatusCode::NOT_FOUND => MyError::NotFound {
+                        endpoint: endpoint_url.to_owned(),
+                    },
+                    StatusCode::TOO_MANY_REQUESTS => {
+                        if let Some(delay) = retry_after {
+                            // Empty all buckets to stop any requests in other threads from continuing
+                            for l in self.inner.scheduler.limiters() {
+                                l.empty();
+                            }
+
+                            info!(
+                                "TOO_MANY_REQUESTS received - Delaying for {} seconds.",
+                                delay.as_secs()
+                            );
+
+                            for l in self.inner.scheduler.limiters() {
+                                l.refill();
+                            }
+                        }
+
+                        let scope = res
+                            .headers()
+                            .get("x-rate-limit-type")
+                            .and_then(|h| h.to_str().ok())
+                            .unwrap_or("unknown")
+                            .to_owned();
+
+                        metrics::global().record_rate_limited(&scope);
+
+                        MyError::RateLimited {
+                            retry_after: retry_after.map(|d| d.as_secs()),
+                            scope,
+                        }
+                    }
+                    status if status.is_server_error() => MyError::ServiceUnavailable {
+                        endpoint: endpoint_url.to_owned(),
+                    },
+                    status => MyError::Other(anyhow!(
+                        "Unexpected status {} for endpoint: {}",
+                        status,
                         endpoint_url
-                    )));
-                }
-                StatusCode::OK => {
-                    return Ok(res.text().await?);
-                }
-                _ => {
-                    //try again in 1 sec
-                    tokio::time::sleep(Duration::from_millis(500)).await;
-                    continue 'outer;
+                    )),
+                };
+
+                if !retry_policy.is_retryable_status(status) {
+                    return Attempt::Done(Err(error));
                 }
+
+                Attempt::Retry(error, retry_after)
             }
         }
     }
 }
 
+/// Outcome of a single attempt at a request.
+enum Attempt {
+    Done(Result<String>),
+    /// Failed in a way the retry policy allows retrying, after the delay asked for by the server
+    /// if any.
+    Retry(MyError, Option<Duration>),
+}
+
 #[derive(Debug, Display, EnumString, EnumVariantNames)]
 #[allow(dead_code, clippy::upper_case_acronyms)]
 pub enum ApiRegion {
@@ -221,9 +611,95 @@ impl ApiRegion {
     }
 }
 
+/// Shares the rate limit budget between requests, letting higher priority requests go first.
+#[derive(Debug)]
+pub struct Scheduler {
+    limiters: Vec<Limiter>,
+    /// Number of requests waiting for rate limit budget, indexed by `Priority`.
+    waiting: [AtomicUsize; Priority::COUNT],
+}
+
+impl Scheduler {
+    pub fn new(limiters: Vec<Limiter>) -> Self {
+        Scheduler {
+            limiters,
+            waiting: Default::default(),
+        }
+    }
+
+    pub fn limiters(&self) -> &[Limiter] {
+        &self.limiters
+    }
+
+    /// Requests left in each rate limit window.
+    pub fn budget(&self) -> Vec<RateLimitBudget> {
+        self.limiters.iter().map(Limiter::budget).collect()
+    }
+
+    /// Waits until requests of `priority` may use the rate limit budget, then takes from it.
+    pub async fn wait_for_budget(&self, priority: Priority) {
+        let index = priority as usize;
+        let started = Instant::now();
+
+        // Held until the budget is taken, so that lower priority requests can't slip in between,
+        // and released even if the request is cancelled while waiting.
+        let _waiting = WaitingGuard::new(&self.waiting[index]);
+
+        loop {
+            let higher_priority_waiting = self.waiting[index + 1..]
+                .iter()
+                .any(|w| w.load(Ordering::Acquire) > 0);
+
+            let has_budget = self
+                .limiters
+                .iter()
+                .all(|l| l.has_budget(priority.reserved_share()));
+
+            if !higher_priority_waiting && has_budget {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        for l in self.limiters.iter() {
+            // Wait until limiters no longer give us an error.
+            while l.take().is_err() {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+
+        metrics::global().record_limiter_wait(started.elapsed());
+    }
+}
+
+/// Counts a request as waiting for as long as it is alive.
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl<'a> WaitingGuard<'a> {
+    fn new(waiting: &'a AtomicUsize) -> Self {
+        waiting.fetch_add(1, Ordering::AcqRel);
+        WaitingGuard(waiting)
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimitBudget {
+    pub remaining: usize,
+    pub size: usize,
+    pub refill_seconds: u64,
+}
+
 #[derive(Debug)]
 pub struct Limiter {
     initial_size: usize,
+    refill_time: u64,
     bucket: Arc<AtomicUsize>,
 }
 
@@ -245,6 +721,7 @@ impl Limiter {
 
         Limiter {
             initial_size: size,
+            refill_time,
             bucket: Arc::clone(&bucket_ptr),
         }
     }
@@ -257,6 +734,21 @@ impl Limiter {
         self.bucket.store(0, Ordering::Release);
     }
 
+    pub fn budget(&self) -> RateLimitBudget {
+        RateLimitBudget {
+            remaining: self.bucket.load(Ordering::Acquire),
+            size: self.initial_size,
+            refill_seconds: self.refill_time,
+        }
+    }
+
+    /// Whether more than `reserved_share` of the bucket is left.
+    pub fn has_budget(&self, reserved_share: f32) -> bool {
+        let reserved = (self.initial_size as f32 * reserved_share) as usize;
+
+        self.bucket.load(Ordering::Acquire) > reserved
+    }
+
     pub fn take(&self) -> Result<()> {
         let current = self.bucket.load(Ordering::Acquire);
 
@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+use crate::analysis::encounters;
+use crate::analysis::smurf::ladder_points;
+use crate::models::champion::ChampionWinRate;
+use crate::models::error::MyError;
+
+/// Columns the players table can show, also usable as sort keys.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Display, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Column {
+    Summoner,
+    Champion,
+    WinRate,
+    Games,
+    OverallWinRate,
+    Rank,
+    Lp,
+    Mastery,
+    Role,
+    Spells,
+    Runes,
+    Level,
+    Premade,
+    MetBefore,
+    Smurf,
+    SmurfFactors,
+}
+
+pub const DEFAULT_COLUMNS: [Column; 9] = [
+    Column::Champion,
+    Column::WinRate,
+    Column::Rank,
+    Column::Role,
+    Column::Spells,
+    Column::Runes,
+    Column::Premade,
+    Column::MetBefore,
+    Column::Smurf,
+];
+
+impl Column {
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Summoner => "Summoner",
+            Column::Champion => "Champion Name",
+            Column::WinRate => "Win Rate",
+            Column::Games => "Games",
+            Column::OverallWinRate => "Overall Win Rate",
+            Column::Rank => "Rank (Solo Queue)",
+            Column::Lp => "LP",
+            Column::Mastery => "Mastery",
+            Column::Role => "Role",
+            Column::Spells => "Spells",
+            Column::Runes => "Runes",
+            Column::Level => "Level",
+            Column::Premade => "Premade",
+            Column::MetBefore => "Met Before",
+            Column::Smurf => "Smurf?",
+            Column::SmurfFactors => "Smurf Factors",
+        }
+    }
+
+    /// Text shown for `player` in this column.
+    pub fn value(&self, player: &ChampionWinRate, verbose: bool) -> String {
+        match self {
+            Column::Summoner => player.summoner_name.clone(),
+            Column::Champion => player.champion_name.clone(),
+            Column::WinRate => player.win_rate_string(),
+            Column::Games => player.total_games.to_string(),
+            Column::OverallWinRate => match (player.overall_win_rate(), &player.solo_queue_rank) {
+                (Some(win_rate), Some(rank)) => {
+                    format!("{}% ({})", win_rate, rank.wins + rank.losses)
+                }
+                _ => String::new(),
+            },
+            Column::Rank => player.rank.clone(),
+            Column::Lp => player
+                .league_points()
+                .map(|lp| lp.to_string())
+                .unwrap_or_default(),
+            Column::Mastery => player
+                .mastery
+                .as_ref()
+                .map(|m| format!("Level {} ({} pts)", m.champion_level, m.champion_points))
+                .unwrap_or_default(),
+            Column::Role => player.role.role_string(),
+            Column::Spells => player.spells.join(" / "),
+            Column::Runes => [player.keystone.as_deref(), player.secondary_tree.as_deref()]
+                .iter()
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" / "),
+            Column::Level => player.summoner_level.to_string(),
+            Column::Premade => player
+                .premade_group
+                .map(|group| format!("■ {}", group))
+                .unwrap_or_default(),
+            Column::MetBefore => encounters::encounters_string(&player.encounters),
+            Column::Smurf => {
+                if player.smurf.flagged {
+                    format!("⚠ {}", player.smurf.score)
+                } else if verbose {
+                    player.smurf.score.to_string()
+                } else {
+                    String::new()
+                }
+            }
+            Column::SmurfFactors => player.smurf.factors.join("\n"),
+        }
+    }
+
+    /// Orders two players by this column's value, lowest first.
+    pub fn compare(&self, a: &ChampionWinRate, b: &ChampionWinRate) -> Ordering {
+        match self {
+            Column::Summoner => a.summoner_name.cmp(&b.summoner_name),
+            Column::Champion => a.champion_name.cmp(&b.champion_name),
+            Column::WinRate => a.win_rate.cmp(&b.win_rate),
+            Column::Games => a.total_games.cmp(&b.total_games),
+            Column::OverallWinRate => a.overall_win_rate().cmp(&b.overall_win_rate()),
+            Column::Rank => rank_value(a).cmp(&rank_value(b)),
+            Column::Lp => a.league_points().cmp(&b.league_points()),
+            Column::Mastery => mastery_points(a).cmp(&mastery_points(b)),
+            Column::Role => a.role.predicted_role.cmp(&b.role.predicted_role),
+            Column::Spells => a.spells.cmp(&b.spells),
+            Column::Runes => a.keystone.cmp(&b.keystone),
+            Column::Level => a.summoner_level.cmp(&b.summoner_level),
+            Column::Premade => a.premade_group.cmp(&b.premade_group),
+            Column::MetBefore => a.encounters.len().cmp(&b.encounters.len()),
+            Column::Smurf | Column::SmurfFactors => a.smurf.score.cmp(&b.smurf.score),
+        }
+    }
+}
+
+fn rank_value(player: &ChampionWinRate) -> Option<i64> {
+    let rank = player.solo_queue_rank.as_ref()?;
+
+    ladder_points(&rank.tier, &rank.rank, rank.league_points)
+}
+
+fn mastery_points(player: &ChampionWinRate) -> Option<i64> {
+    player.mastery.as_ref().map(|m| m.champion_points)
+}
+
+/// A column to sort players by, written as `column` or `column:asc|desc` on the command line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: Column,
+    pub descending: bool,
+}
+
+pub const DEFAULT_SORT: [SortKey; 1] = [SortKey {
+    column: Column::WinRate,
+    descending: true,
+}];
+
+impl FromStr for SortKey {
+    type Err = MyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (column, direction) = s.split_once(':').unwrap_or((s, "asc"));
+
+        let column = column
+            .parse()
+            .map_err(|_| anyhow!("Unknown sort column: {}", column))?;
+
+        let descending = match direction {
+            "asc" => false,
+            "desc" => true,
+            _ => return Err(anyhow!("Unknown sort direction: {}", direction).into()),
+        };
+
+        Ok(SortKey { column, descending })
+    }
+}
+
+/// Sorts players by team, then by each of `keys` in turn.
+pub fn sort_players(players: &mut [ChampionWinRate], keys: &[SortKey]) {
+    players.sort_by(|a, b| {
+        keys.iter()
+            .fold(a.team_id.cmp(&b.team_id), |ordering, key| {
+                ordering.then_with(|| {
+                    let ordering = key.column.compare(a, b);
+
+                    if key.descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+            })
+    });
+}
@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context;
+use hyper::header::{CACHE_CONTROL, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+use serde::Serialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::Duration;
+
+use league_notify::events::{LpSnapshot, TrackerEvent};
+use league_notify::report::GameReport;
+use league_notify::util::now_millis;
+use league_notify::Result;
+
+/// Number of games kept in the session history.
+const HISTORY_SIZE: usize = 50;
+
+/// Interval of the comments sent on idle event streams so proxies don't close them.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+const INDEX_HTML: &str = include_str!("dashboard.html");
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryEntry {
+    /// Epoch milliseconds.
+    pub detected_at: i64,
+    pub summary: String,
+    pub report: Arc<GameReport>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct DashboardState {
+    /// Game the tracked summoner is currently in, if any.
+    pub current_game_id: Option<u64>,
+    /// Report of the current game, once it has finished loading.
+    pub current_game: Option<Arc<GameReport>>,
+    pub history: VecDeque<HistoryEntry>,
+    pub lp_history: Vec<LpSnapshot>,
+}
+
+impl DashboardState {
+    /// Applies `event` to the state, returning whether the state changed.
+    pub fn apply(&mut self, event: &TrackerEvent) -> bool {
+        match event {
+            TrackerEvent::GameDetected { game_id } => {
+                self.current_game_id = Some(*game_id);
+                self.current_game = None;
+            }
+            TrackerEvent::ReportLoaded { report } => {
+                self.current_game = Some(Arc::clone(report));
+
+                self.history.push_front(HistoryEntry {
+                    detected_at: now_millis(),
+                    summary: report.summary(),
+                    report: Arc::clone(report),
+                });
+                self.history.truncate(HISTORY_SIZE);
+            }
+            TrackerEvent::GameEnded { .. } => {
+                self.current_game_id = None;
+                self.current_game = None;
+            }
+            TrackerEvent::RankUpdated { snapshot } => self.lp_history.push(snapshot.clone()),
+            TrackerEvent::Polled { .. } => return false,
+        }
+
+        true
+    }
+}
+
+/// Serves the dashboard on `addr` until the server fails, updating it from `events`.
+pub async fn serve(addr: SocketAddr, events: broadcast::Sender<TrackerEvent>) -> Result<()> {
+    let state = Arc::new(Mutex::new(DashboardState::default()));
+
+    // Events are only forwarded to clients once they have been applied to the state, so that
+    // clients refreshing on an event never see stale state.
+    let (updates, _) = broadcast::channel(16);
+
+    let mut receiver = events.subscribe();
+    let updated_state = Arc::clone(&state);
+    let forwarded_updates = updates.clone();
+
+    tokio::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    let changed = updated_state
+                        .lock()
+                        .expect("Dashboard state lock poisoned")
+                        .apply(&event);
+
+                    if changed {
+                        let _ = forwarded_updates.send(event);
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Dashboard missed {} tracker events", skipped)
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    let make_service = make_service_fn(move |_| {
+        let state = Arc::clone(&state);
+        let updates = updates.clone();
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| {
+                handle(req, Arc::clone(&state), updates.clone())
+            }))
+        }
+    });
+
+    info!("Dashboard listening on http://{}", addr);
+
+    Server::try_bind(&addr)
+        .context("Could not bind dashboard address")?
+        .serve(make_service)
+        .await
+        .context("Dashboard server failed")?;
+
+    Ok(())
+}
+
+async fn handle(
+    req: Request<Body>,
+    state: Arc<Mutex<DashboardState>>,
+    updates: broadcast::Sender<TrackerEvent>,
+) -> std::result::Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET {
+        return Ok(status(StatusCode::METHOD_NOT_ALLOWED));
+    }
+
+    let response = match req.uri().path() {
+        "/" => Response::builder()
+            .header(CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from(INDEX_HTML)),
+        "/api/game" => json(
+            &state
+                .lock()
+                .expect("Dashboard state lock poisoned")
+                .current_game,
+        ),
+        "/api/history" => json(&state.lock().expect("Dashboard state lock poisoned").history),
+        "/api/lp" => json(
+            &state
+                .lock()
+                .expect("Dashboard state lock poisoned")
+                .lp_history,
+        ),
+        "/api/state" => json(&*state.lock().expect("Dashboard state lock poisoned")),
+        "/api/events" => event_stream(updates.subscribe()),
+        _ => return Ok(status(StatusCode::NOT_FOUND)),
+    };
+
+    Ok(response.unwrap_or_else(|_| status(StatusCode::INTERNAL_SERVER_ERROR)))
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> hyper::http::Result<Response<Body>> {
+    match serde_json::to_string(value) {
+        Ok(body) => Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body)),
+        Err(_) => Ok(status(StatusCode::INTERNAL_SERVER_ERROR)),
+    }
+}
+
+/// Streams tracker events as Server-Sent Events until the client disconnects.
+fn event_stream(
+    mut receiver: broadcast::Receiver<TrackerEvent>,
+) -> hyper::http::Result<Response<Body>> {
+    let (mut sender, body) = Body::channel();
+
+    tokio::spawn(async move {
+        loop {
+            let chunk = tokio::select! {
+                event = receiver.recv() => match event {
+                    Ok(event) => match serde_json::to_string(&event) {
+                        Ok(data) => format!("data: {}\n\n", data),
+                        Err(e) => {
+                            error!("Could not serialize tracker event: {}", e);
+                            continue;
+                        }
+                    },
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                },
+                _ = tokio::time::sleep(KEEP_ALIVE_INTERVAL) => ": keep-alive\n\n".to_owned(),
+            };
+
+            if sender.send_data(chunk.into()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Response::builder()
+        .header(CONTENT_TYPE, "text/event-stream")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(body)
+}
+
+fn status(status: StatusCode) -> Response<Body> {
+    let mut response = Response::new(Body::from(status.to_string()));
+    *response.status_mut() = status;
+    response
+}
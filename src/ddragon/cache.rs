@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::Result;
+
+/// Localized files that have to be present for a cached version to be usable offline.
+const LOCALIZED_FILES: [&str; 4] = [
+    "champion.json",
+    "item.json",
+    "summoner.json",
+    "runesReforged.json",
+];
+
+/// Files shared by all locales that have to be present for a cached version to be usable offline.
+const SHARED_FILES: [&str; 2] = ["queues.json", "maps.json"];
+
+/// Directory of files that are the same in every version, such as splash art. It isn't a version,
+/// so it is never listed or pruned.
+pub const UNVERSIONED_DIR: &str = "unversioned";
+
+/// On-disk cache of Data Dragon files, stored as `<root>/<version>/<locale>/<file>`, or
+/// `<root>/<version>/<file>` for files that aren't localized such as images, with
+/// `UNVERSIONED_DIR` standing in for the version of unversioned files.
+#[derive(Debug)]
+pub struct DDragonCache {
+    root: PathBuf,
+}
+
+impl DDragonCache {
+    pub fn new() -> Result<Self> {
+        let root = dirs::cache_dir()
+            .context("Could not find a cache directory.")?
+            .join("league_notify")
+            .join("ddragon");
+
+        fs::create_dir_all(&root)?;
+
+        Ok(DDragonCache { root })
+    }
+
+    pub fn read(&self, version: &str, file_name: &str) -> Option<String> {
+        fs::read_to_string(self.root.join(version).join(file_name)).ok()
+    }
+
+    pub fn read_bytes(&self, version: &str, file_name: &str) -> Option<Vec<u8>> {
+        fs::read(self.root.join(version).join(file_name)).ok()
+    }
+
+    pub fn write(&self, version: &str, file_name: &str, contents: impl AsRef<[u8]>) -> Result<()> {
+        let path = self.root.join(version).join(file_name);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    pub fn is_complete(&self, version: &str, locale: &str) -> bool {
+        let dir = self.root.join(version);
+
+        SHARED_FILES.iter().all(|f| dir.join(f).is_file())
+            && LOCALIZED_FILES
+                .iter()
+                .all(|f| dir.join(locale).join(f).is_file())
+    }
+
+    /// Locales with complete static data cached for `version`.
+    pub fn locales(&self, version: &str) -> Result<Vec<String>> {
+        let mut locales = fs::read_dir(self.root.join(version))?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|l| self.is_complete(version, l))
+            .collect::<Vec<_>>();
+
+        locales.sort();
+
+        Ok(locales)
+    }
+
+    /// Cached versions, newest first.
+    pub fn versions(&self) -> Result<Vec<String>> {
+        let mut versions = fs::read_dir(&self.root)?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|v| parse_version(v).is_some())
+            .collect::<Vec<_>>();
+
+        versions.sort_by_key(|v| std::cmp::Reverse(parse_version(v)));
+
+        Ok(versions)
+    }
+
+    /// Newest cached version that has all static data files for `locale`.
+    pub fn latest_complete_version(&self, locale: &str) -> Result<Option<String>> {
+        Ok(self
+            .versions()?
+            .into_iter()
+            .find(|v| self.is_complete(v, locale)))
+    }
+
+    /// Removes all but the newest `keep` versions, returning the removed versions.
+    pub fn prune(&self, keep: usize) -> Result<Vec<String>> {
+        let removed = self.versions()?.into_iter().skip(keep).collect::<Vec<_>>();
+
+        for version in removed.iter() {
+            fs::remove_dir_all(self.root.join(version))?;
+        }
+
+        Ok(removed)
+    }
+}
+
+pub fn parse_version(version: &str) -> Option<Vec<u32>> {
+    version.split('.').map(|p| p.parse().ok()).collect()
+}
@@ -1 +1,4 @@
+pub mod cache;
+pub mod sprite;
+pub mod static_data;
 pub mod updater;
@@ -0,0 +1,28 @@
+use std::io::Cursor;
+
+use anyhow::Context;
+use image::ImageFormat;
+
+use crate::models::ddragon_champions::Image;
+use crate::Result;
+
+/// Crops the image described by `image` out of its PNG sprite sheet, returning it as a PNG.
+pub fn crop_sprite(sprite_sheet: &[u8], image: &Image) -> Result<Vec<u8>> {
+    let sheet = image::load_from_memory_with_format(sprite_sheet, ImageFormat::Png)
+        .context("Failed to decode sprite sheet.")?;
+
+    let cropped = sheet.crop_imm(
+        image.x as u32,
+        image.y as u32,
+        image.w as u32,
+        image.h as u32,
+    );
+
+    let mut png = Vec::new();
+
+    cropped
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .context("Failed to encode sprite.")?;
+
+    Ok(png)
+}
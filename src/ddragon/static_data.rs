@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::models::ddragon_champions::{Champion, ChampionData};
+use crate::models::ddragon_items::{Item, ItemData};
+use crate::models::ddragon_runes::{Rune, RuneData, RuneTree};
+use crate::models::ddragon_summoner_spells::{SummonerSpell, SummonerSpellData};
+use crate::models::static_docs::{Map, Queue};
+use crate::Result;
+
+/// All static game data for a single patch, indexed for id lookups.
+#[derive(Debug)]
+pub struct StaticData {
+    pub version: String,
+    pub champions: ChampionData,
+    pub items: ItemData,
+    pub summoner_spells: SummonerSpellData,
+    pub runes: RuneData,
+    pub queues: Vec<Queue>,
+    pub maps: Vec<Map>,
+    item_index: HashMap<i64, usize>,
+    summoner_spell_index: HashMap<i64, usize>,
+    rune_index: HashMap<i64, (usize, usize, usize)>,
+    rune_tree_index: HashMap<i64, usize>,
+    queue_index: HashMap<i64, usize>,
+    map_index: HashMap<i64, usize>,
+}
+
+impl StaticData {
+    pub fn new(
+        champions: ChampionData,
+        items: ItemData,
+        summoner_spells: SummonerSpellData,
+        runes: RuneData,
+        queues: Vec<Queue>,
+        maps: Vec<Map>,
+    ) -> Self {
+        let item_index = index(&items.item_list, |i| i.id);
+        let summoner_spell_index = index(&summoner_spells.summoner_spell_list, |s| s.key);
+        let rune_tree_index = index(&runes.trees, |t| t.id);
+        let queue_index = index(&queues, |q| q.queue_id);
+        let map_index = index(&maps, |m| m.map_id);
+
+        let mut rune_index = HashMap::new();
+
+        for (tree_i, tree) in runes.trees.iter().enumerate() {
+            for (slot_i, slot) in tree.slots.iter().enumerate() {
+                for (rune_i, rune) in slot.runes.iter().enumerate() {
+                    rune_index.insert(rune.id, (tree_i, slot_i, rune_i));
+                }
+            }
+        }
+
+        StaticData {
+            version: champions.version.clone(),
+            champions,
+            items,
+            summoner_spells,
+            runes,
+            queues,
+            maps,
+            item_index,
+            summoner_spell_index,
+            rune_index,
+            rune_tree_index,
+            queue_index,
+            map_index,
+        }
+    }
+
+    pub fn champion(&self, key: i64) -> Result<&Champion> {
+        self.champions.champion(key)
+    }
+
+    pub fn champion_name(&self, key: i64) -> String {
+        self.champions.champion_name(key)
+    }
+
+    pub fn item(&self, id: i64) -> Option<&Item> {
+        self.item_index.get(&id).map(|&i| &self.items.item_list[i])
+    }
+
+    pub fn summoner_spell(&self, key: i64) -> Option<&SummonerSpell> {
+        self.summoner_spell_index
+            .get(&key)
+            .map(|&i| &self.summoner_spells.summoner_spell_list[i])
+    }
+
+    pub fn rune(&self, id: i64) -> Option<&Rune> {
+        self.rune_index
+            .get(&id)
+            .map(|&(tree, slot, rune)| &self.runes.trees[tree].slots[slot].runes[rune])
+    }
+
+    pub fn rune_tree(&self, id: i64) -> Option<&RuneTree> {
+        self.rune_tree_index.get(&id).map(|&i| &self.runes.trees[i])
+    }
+
+    pub fn queue(&self, id: i64) -> Option<&Queue> {
+        self.queue_index.get(&id).map(|&i| &self.queues[i])
+    }
+
+    pub fn map(&self, id: i64) -> Option<&Map> {
+        self.map_index.get(&id).map(|&i| &self.maps[i])
+    }
+}
+
+fn index<T>(entries: &[T], id: impl Fn(&T) -> i64) -> HashMap<i64, usize> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| (id(entry), i))
+        .collect()
+}
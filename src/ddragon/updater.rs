@@ -1,55 +1,378 @@
+use anyhow::{anyhow, Context};
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 
+use crate::ddragon::cache::{DDragonCache, UNVERSIONED_DIR};
+use crate::ddragon::sprite;
+use crate::ddragon::static_data::StaticData;
 use crate::endpoints::ddragon;
-use crate::models::ddragon_champions::ChampionData;
+use crate::models::ddragon_champions::{Champion, ChampionData, Image};
+use crate::models::ddragon_items::{Item, ItemData};
+use crate::models::ddragon_runes::RuneData;
+use crate::models::ddragon_summoner_spells::SummonerSpellData;
+use crate::models::error::MyError;
+use crate::models::static_docs::{Map, Queue};
+use crate::retry::{self, RetryPolicy};
 use crate::Result;
 
 #[derive(Debug)]
 pub struct DDragonUpdater {
     client: Client,
+    retry_policy: RetryPolicy,
+    /// `None` if the cache directory can't be used, every file is then downloaded.
+    cache: Option<DDragonCache>,
     version: String,
+    locale: String,
 }
 
 impl DDragonUpdater {
-    pub async fn new() -> Result<Self> {
+    /// Uses the latest version reported by Data Dragon, or the newest cached version if Data
+    /// Dragon can't be reached. All localized data is downloaded in `locale`.
+    pub async fn new(locale: &str, retry_policy: &RetryPolicy) -> Result<Self> {
+        // Checked before anything else, as the locale is also part of cache paths.
+        if !is_locale_format(locale) {
+            return Err(MyError::Other(anyhow!(
+                "Invalid locale {} - Expected a language and country code such as en_US",
+                locale
+            )));
+        }
+
         let client = Client::new();
+        let cache = open_cache();
 
-        let res = client
-            .get(&ddragon::DDragonEndpoint::Version.url())
-            .send()
-            .await?
-            .text()
-            .await?;
+        let version = match Self::latest_version(&client, retry_policy).await {
+            Ok(version) => {
+                Self::validate_locale(&client, retry_policy, locale).await?;
+                version
+            }
+            Err(e) => match cache
+                .as_ref()
+                .map(|c| c.latest_complete_version(locale))
+                .transpose()?
+                .flatten()
+            {
+                Some(version) => {
+                    warn!(
+                        "Could not reach DDragon ({}) - Using cached version {}.",
+                        e, version
+                    );
+                    version
+                }
+                None => return Err(e),
+            },
+        };
+
+        Ok(DDragonUpdater {
+            client,
+            retry_policy: retry_policy.clone(),
+            cache,
+            version,
+            locale: locale.to_owned(),
+        })
+    }
+
+    /// Uses a specific version, e.g. one derived from a match's game version, without checking
+    /// Data Dragon for the latest version.
+    pub fn with_version(version: &str, locale: &str, retry_policy: &RetryPolicy) -> Self {
+        DDragonUpdater {
+            client: Client::new(),
+            retry_policy: retry_policy.clone(),
+            cache: open_cache(),
+            version: version.to_owned(),
+            locale: locale.to_owned(),
+        }
+    }
+
+    async fn validate_locale(
+        client: &Client,
+        retry_policy: &RetryPolicy,
+        locale: &str,
+    ) -> Result<()> {
+        let res = retry::get(
+            client,
+            retry_policy,
+            &ddragon::DDragonEndpoint::Languages.url(),
+        )
+        .await?
+        .text()
+        .await?;
+
+        let languages = serde_json::from_str::<Vec<String>>(&res)?;
+
+        if !languages.iter().any(|l| l == locale) {
+            return Err(MyError::Other(anyhow!(
+                "Unsupported locale {} - Expected one of: {}",
+                locale,
+                languages.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn latest_version(client: &Client, retry_policy: &RetryPolicy) -> Result<String> {
+        let res = retry::get(
+            client,
+            retry_policy,
+            &ddragon::DDragonEndpoint::Version.url(),
+        )
+        .await?
+        .text()
+        .await?;
 
         let version = serde_json::from_str::<Vec<String>>(&res)?
-            .get(0)
-            .expect("Missing version data from DDragon.")
+            .first()
+            .context("Missing version data from DDragon.")?
             .to_string();
 
-        Ok(DDragonUpdater { client, version })
+        Ok(version)
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    pub async fn download_static_data(&self) -> Result<StaticData> {
+        let (champions, items, summoner_spells, runes, queues, maps) = tokio::try_join!(
+            self.download_champions(),
+            self.download_items(),
+            self.download_summoner_spells(),
+            self.download_runes(),
+            self.download_queues(),
+            self.download_maps(),
+        )?;
+
+        Ok(StaticData::new(
+            champions,
+            items,
+            summoner_spells,
+            runes,
+            queues,
+            maps,
+        ))
+    }
+
+    pub async fn download_champions(&self) -> Result<ChampionData> {
+        let data = self
+            .call_endpoint(&ddragon::DDragonEndpoint::ChampionData(
+                &self.version,
+                &self.locale,
+            ))
+            .await?;
+
+        Ok(data)
+    }
+
+    pub async fn download_summoner_spells(&self) -> Result<SummonerSpellData> {
+        let data = self
+            .call_endpoint(&ddragon::DDragonEndpoint::SummonerSpellData(
+                &self.version,
+                &self.locale,
+            ))
+            .await?;
+
+        Ok(data)
+    }
+
+    pub async fn download_runes(&self) -> Result<RuneData> {
+        let data = self
+            .call_endpoint(&ddragon::DDragonEndpoint::RuneData(
+                &self.version,
+                &self.locale,
+            ))
+            .await?;
+
+        Ok(data)
+    }
+
+    pub async fn download_items(&self) -> Result<ItemData> {
+        let data = self
+            .call_endpoint(&ddragon::DDragonEndpoint::ItemData(
+                &self.version,
+                &self.locale,
+            ))
+            .await?;
+
+        Ok(data)
     }
 
-    pub async fn download_latest_champions(&self) -> Result<ChampionData> {
+    pub async fn download_queues(&self) -> Result<Vec<Queue>> {
         let data = self
-            .call_endpoint(&ddragon::DDragonEndpoint::ChampionData(&self.version))
+            .call_endpoint(&ddragon::DDragonEndpoint::Queues)
             .await?;
 
         Ok(data)
     }
 
+    pub async fn download_maps(&self) -> Result<Vec<Map>> {
+        let data = self.call_endpoint(&ddragon::DDragonEndpoint::Maps).await?;
+
+        Ok(data)
+    }
+
+    pub async fn champion_square(&self, champion: &Champion) -> Result<Vec<u8>> {
+        self.call_image_endpoint(&ddragon::DDragonEndpoint::ChampionSquare(
+            &self.version,
+            &champion.image.full,
+        ))
+        .await
+    }
+
+    pub async fn champion_splash(&self, champion: &Champion, skin: i64) -> Result<Vec<u8>> {
+        self.call_image_endpoint(&ddragon::DDragonEndpoint::ChampionSplash(
+            &champion.id,
+            skin,
+        ))
+        .await
+    }
+
+    pub async fn item_icon(&self, item: &Item) -> Result<Vec<u8>> {
+        self.call_image_endpoint(&ddragon::DDragonEndpoint::ItemIcon(
+            &self.version,
+            &item.image.full,
+        ))
+        .await
+    }
+
+    pub async fn profile_icon(&self, icon_id: i64) -> Result<Vec<u8>> {
+        self.call_image_endpoint(&ddragon::DDragonEndpoint::ProfileIcon(
+            &self.version,
+            icon_id,
+        ))
+        .await
+    }
+
+    /// Crops `image` out of its sprite sheet, so a whole group of small icons only needs a single
+    /// download.
+    pub async fn sprite_image(&self, image: &Image) -> Result<Vec<u8>> {
+        let sprite_sheet = self
+            .call_image_endpoint(&ddragon::DDragonEndpoint::Sprite(
+                &self.version,
+                &image.sprite,
+            ))
+            .await?;
+
+        sprite::crop_sprite(&sprite_sheet, image)
+    }
+
+    async fn call_image_endpoint(
+        &self,
+        endpoint: &ddragon::DDragonEndpoint<'_>,
+    ) -> Result<Vec<u8>> {
+        let file_name = endpoint.file_name();
+        let version = if endpoint.is_versioned() {
+            &self.version
+        } else {
+            UNVERSIONED_DIR
+        };
+
+        if let Some(bytes) = self
+            .cache
+            .as_ref()
+            .zip(file_name.as_ref())
+            .and_then(|(cache, f)| cache.read_bytes(version, f))
+        {
+            return Ok(bytes);
+        }
+
+        let bytes = retry::get(&self.client, &self.retry_policy, &endpoint.url())
+            .await?
+            .bytes()
+            .await?
+            .to_vec();
+
+        if let Some(file_name) = file_name {
+            self.write_cache(version, &file_name, &bytes);
+        }
+
+        Ok(bytes)
+    }
+
     async fn call_endpoint<T: DeserializeOwned>(
         &self,
         endpoint: &ddragon::DDragonEndpoint<'_>,
     ) -> Result<T> {
-        let res = self
-            .client
-            .get(&endpoint.url())
-            .send()
+        let file_name = endpoint.file_name();
+
+        if let Some(res) = self
+            .cache
+            .as_ref()
+            .zip(file_name.as_ref())
+            .and_then(|(cache, f)| cache.read(&self.version, f))
+        {
+            match serde_json::from_str::<T>(&res) {
+                Ok(data) => return Ok(data),
+                Err(e) => warn!(
+                    "Ignoring invalid cached DDragon file {}: {}",
+                    endpoint.url(),
+                    e
+                ),
+            }
+        }
+
+        let res = retry::get(&self.client, &self.retry_policy, &endpoint.url())
             .await?
             .text()
             .await?;
 
-        Ok(serde_json::from_str::<T>(&res)?)
+        let data = serde_json::from_str::<T>(&res)
+            .map_err(|source| MyError::deserialize(endpoint.url(), &res, source))?;
+
+        if let Some(file_name) = file_name {
+            self.write_cache(&self.version, &file_name, &res);
+        }
+
+        Ok(data)
+    }
+
+    /// A file that can't be cached is downloaded again next time, so this only logs failures.
+    fn write_cache(&self, version: &str, file_name: &str, contents: impl AsRef<[u8]>) {
+        if let Some(cache) = &self.cache {
+            if let Err(e) = cache.write(version, file_name, contents) {
+                warn!("Could not cache DDragon file {}: {}", file_name, e);
+            }
+        }
+    }
+}
+
+fn open_cache() -> Option<DDragonCache> {
+    match DDragonCache::new() {
+        Ok(cache) => Some(cache),
+        Err(e) => {
+            warn!(
+                "Could not open the DDragon cache ({}) - Downloading all files.",
+                e
+            );
+            None
+        }
     }
 }
+
+/// Whether `locale` looks like a Data Dragon locale such as `en_US`, a lowercase language code and
+/// an uppercase country code.
+pub fn is_locale_format(locale: &str) -> bool {
+    match locale.split_once('_') {
+        Some((language, country)) => {
+            language.len() == 2
+                && country.len() == 2
+                && language.bytes().all(|b| b.is_ascii_lowercase())
+                && country.bytes().all(|b| b.is_ascii_uppercase())
+        }
+        None => false,
+    }
+}
+
+/// Converts a match's game version such as `13.1.482.3244` to the Data Dragon version of that
+/// patch, `13.1.1`.
+pub fn ddragon_version(game_version: &str) -> Option<String> {
+    let mut parts = game_version.split('.');
+
+    let major = parts.next()?.parse::<u32>().ok()?;
+    let minor = parts.next()?.parse::<u32>().ok()?;
+
+    Some(format!("{}.{}.1", major, minor))
+}
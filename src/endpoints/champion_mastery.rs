@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use super::{CacheTtl, Endpoint};
+
+const CHAMPION_MASTERY_ENDPOINT: &str = "lol/champion-mastery/v4";
+
+pub enum ChampionMasteryEndpoint<'a> {
+    BySummonerIdAndChampion(&'a str, i64),
+}
+
+impl Endpoint for ChampionMasteryEndpoint<'_> {
+    fn cache_ttl(&self) -> CacheTtl {
+        CacheTtl::For(Duration::from_secs(5 * 60))
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ChampionMasteryEndpoint::BySummonerIdAndChampion(..) => {
+                "champion-mastery/v4/champion-masteries/by-summoner/by-champion"
+            }
+        }
+    }
+
+    fn url(self) -> String {
+        match self {
+            ChampionMasteryEndpoint::BySummonerIdAndChampion(
+                encrypted_summoner_id,
+                champion_id,
+            ) => {
+                format!(
+                    "{}/champion-masteries/by-summoner/{}/by-champion/{}",
+                    CHAMPION_MASTERY_ENDPOINT, encrypted_summoner_id, champion_id
+                )
+            }
+        }
+    }
+}
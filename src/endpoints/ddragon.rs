@@ -1,18 +1,109 @@
 const DDRAGON_URL: &str = "https://ddragon.leagueoflegends.com";
+const STATIC_DOCS_URL: &str = "https://static.developer.riotgames.com/docs/lol";
 
+pub const DEFAULT_LOCALE: &str = "en_US";
+
+/// Versioned data endpoints take a version followed by a locale such as `en_US`.
 #[allow(unused)]
 pub enum DDragonEndpoint<'a> {
     Version,
-    ChampionData(&'a str),
+    Languages,
+    ChampionData(&'a str, &'a str),
+    SummonerSpellData(&'a str, &'a str),
+    RuneData(&'a str, &'a str),
+    ItemData(&'a str, &'a str),
+    Queues,
+    Maps,
+    /// Version and image file name, e.g. `Aatrox.png`.
+    ChampionSquare(&'a str, &'a str),
+    /// Champion id (e.g. `Aatrox`) and skin number.
+    ChampionSplash(&'a str, i64),
+    /// Version and image file name, e.g. `1001.png`.
+    ItemIcon(&'a str, &'a str),
+    /// Version and profile icon id.
+    ProfileIcon(&'a str, i64),
+    /// Version and sprite sheet file name, e.g. `champion0.png`.
+    Sprite(&'a str, &'a str),
 }
 
 impl DDragonEndpoint<'_> {
     pub fn url(&self) -> String {
         match self {
             DDragonEndpoint::Version => format!("{}/api/versions.json", DDRAGON_URL),
-            DDragonEndpoint::ChampionData(version) => {
-                format!("{}/cdn/{}/data/en_US/champion.json", DDRAGON_URL, version)
+            DDragonEndpoint::Languages => format!("{}/cdn/languages.json", DDRAGON_URL),
+            DDragonEndpoint::ChampionData(version, locale) => {
+                format!(
+                    "{}/cdn/{}/data/{}/champion.json",
+                    DDRAGON_URL, version, locale
+                )
+            }
+            DDragonEndpoint::SummonerSpellData(version, locale) => {
+                format!(
+                    "{}/cdn/{}/data/{}/summoner.json",
+                    DDRAGON_URL, version, locale
+                )
+            }
+            DDragonEndpoint::RuneData(version, locale) => {
+                format!(
+                    "{}/cdn/{}/data/{}/runesReforged.json",
+                    DDRAGON_URL, version, locale
+                )
+            }
+            DDragonEndpoint::ItemData(version, locale) => {
+                format!("{}/cdn/{}/data/{}/item.json", DDRAGON_URL, version, locale)
+            }
+            DDragonEndpoint::Queues => format!("{}/queues.json", STATIC_DOCS_URL),
+            DDragonEndpoint::Maps => format!("{}/maps.json", STATIC_DOCS_URL),
+            DDragonEndpoint::ChampionSquare(version, file) => {
+                format!("{}/cdn/{}/img/champion/{}", DDRAGON_URL, version, file)
+            }
+            DDragonEndpoint::ChampionSplash(champion_id, skin) => format!(
+                "{}/cdn/img/champion/splash/{}_{}.jpg",
+                DDRAGON_URL, champion_id, skin
+            ),
+            DDragonEndpoint::ItemIcon(version, file) => {
+                format!("{}/cdn/{}/img/item/{}", DDRAGON_URL, version, file)
+            }
+            DDragonEndpoint::ProfileIcon(version, icon_id) => {
+                format!(
+                    "{}/cdn/{}/img/profileicon/{}.png",
+                    DDRAGON_URL, version, icon_id
+                )
+            }
+            DDragonEndpoint::Sprite(version, file) => {
+                format!("{}/cdn/{}/img/sprite/{}", DDRAGON_URL, version, file)
+            }
+        }
+    }
+
+    /// Whether the response can change between versions. Splash art is only served from an
+    /// unversioned URL, so it is cached outside of the version directories.
+    pub fn is_versioned(&self) -> bool {
+        !matches!(self, DDragonEndpoint::ChampionSplash(..))
+    }
+
+    /// Path the response is cached under within its version, `None` if it should always be
+    /// downloaded.
+    pub fn file_name(&self) -> Option<String> {
+        match self {
+            DDragonEndpoint::Version | DDragonEndpoint::Languages => None,
+            DDragonEndpoint::ChampionData(_, locale) => Some(format!("{}/champion.json", locale)),
+            DDragonEndpoint::SummonerSpellData(_, locale) => {
+                Some(format!("{}/summoner.json", locale))
+            }
+            DDragonEndpoint::RuneData(_, locale) => Some(format!("{}/runesReforged.json", locale)),
+            DDragonEndpoint::ItemData(_, locale) => Some(format!("{}/item.json", locale)),
+            DDragonEndpoint::Queues => Some("queues.json".to_owned()),
+            DDragonEndpoint::Maps => Some("maps.json".to_owned()),
+            DDragonEndpoint::ChampionSquare(_, file) => Some(format!("img/champion/{}", file)),
+            DDragonEndpoint::ChampionSplash(champion_id, skin) => {
+                Some(format!("img/champion/splash/{}_{}.jpg", champion_id, skin))
+            }
+            DDragonEndpoint::ItemIcon(_, file) => Some(format!("img/item/{}", file)),
+            DDragonEndpoint::ProfileIcon(_, icon_id) => {
+                Some(format!("img/profileicon/{}.png", icon_id))
             }
+            DDragonEndpoint::Sprite(_, file) => Some(format!("img/sprite/{}", file)),
         }
     }
 }
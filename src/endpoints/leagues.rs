@@ -1,4 +1,6 @@
-use super::Endpoint;
+use std::time::Duration;
+
+use super::{CacheTtl, Endpoint};
 
 const LEAGUES_ENDPOINT: &str = "lol/league/v4";
 
@@ -8,6 +10,16 @@ pub enum LeagueRankEndpoint<'a> {
 }
 
 impl Endpoint for LeagueRankEndpoint<'_> {
+    fn cache_ttl(&self) -> CacheTtl {
+        CacheTtl::For(Duration::from_secs(5 * 60))
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            LeagueRankEndpoint::BySummonerId(_) => "league/v4/entries/by-summoner",
+        }
+    }
+
     fn url(self) -> String {
         match self {
             LeagueRankEndpoint::BySummonerId(encrypted_summoner_id) => format!(
@@ -2,7 +2,7 @@ use std::fmt::Write;
 
 use serde::Serialize;
 
-use super::Endpoint;
+use super::{CacheTtl, Endpoint};
 
 const MATCH_ENDPOINT: &str = "lol/match/v5/matches";
 
@@ -26,6 +26,22 @@ pub struct ByPuiidParams {
 }
 
 impl Endpoint for MatchEndpoint<'_> {
+    fn cache_ttl(&self) -> CacheTtl {
+        match self {
+            // New matches are added to the list, finished matches never change.
+            MatchEndpoint::ByPuuid(..) => CacheTtl::Never,
+            MatchEndpoint::ByMatchId(_) | MatchEndpoint::TimelineByMatchId(_) => CacheTtl::Forever,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            MatchEndpoint::ByPuuid(..) => "match/v5/matches/by-puuid/ids",
+            MatchEndpoint::ByMatchId(_) => "match/v5/matches",
+            MatchEndpoint::TimelineByMatchId(_) => "match/v5/matches/timeline",
+        }
+    }
+
     fn url(self) -> String {
         match self {
             MatchEndpoint::ByPuuid(puuid, params) => {
@@ -1,9 +1,27 @@
+pub mod champion_mastery;
 pub mod ddragon;
 pub mod leagues;
 pub mod lol_match;
 pub mod spectator;
 pub mod summoner;
 
+use std::time::Duration;
+
+/// How long responses of an endpoint are kept in the response cache.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CacheTtl {
+    Never,
+    For(Duration),
+    Forever,
+}
+
 pub trait Endpoint {
     fn url(self) -> String;
+
+    /// Route of the endpoint without any ids, e.g. for labelling metrics.
+    fn name(&self) -> &'static str;
+
+    fn cache_ttl(&self) -> CacheTtl {
+        CacheTtl::Never
+    }
 }
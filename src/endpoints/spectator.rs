@@ -9,6 +9,13 @@ pub enum SpectatorEndpoint<'a> {
 }
 
 impl Endpoint for SpectatorEndpoint<'_> {
+    fn name(&self) -> &'static str {
+        match self {
+            SpectatorEndpoint::BySummonerId(_) => "spectator/v4/active-games/by-summoner",
+            SpectatorEndpoint::FeaturedGames => "spectator/v4/featured-games",
+        }
+    }
+
     fn url(self) -> String {
         match self {
             SpectatorEndpoint::BySummonerId(encrypted_summoner_id) => format!(
@@ -1,4 +1,6 @@
-use super::Endpoint;
+use std::time::Duration;
+
+use super::{CacheTtl, Endpoint};
 
 const SUMMONER_ENDPOINT: &str = "lol/summoner/v4/summoners";
 
@@ -11,6 +13,19 @@ pub enum SummonerEndpointBy<'a> {
 }
 
 impl Endpoint for SummonerEndpointBy<'_> {
+    fn cache_ttl(&self) -> CacheTtl {
+        CacheTtl::For(Duration::from_secs(60 * 60))
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            SummonerEndpointBy::Account(_) => "summoner/v4/summoners/by-account",
+            SummonerEndpointBy::Name(_) => "summoner/v4/summoners/by-name",
+            SummonerEndpointBy::Puuid(_) => "summoner/v4/summoners/by-puuid",
+            SummonerEndpointBy::SummonerId(_) => "summoner/v4/summoners",
+        }
+    }
+
     fn url(self) -> String {
         match self {
             SummonerEndpointBy::Account(encrypted_account_id) => {
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::smurf::ladder_points;
+use crate::api::RateLimitBudget;
+use crate::models::leagues::LeagueRank;
+use crate::report::GameReport;
+use crate::response_cache::CacheStats;
+use crate::util::now_millis;
+
+/// Events published by the tracker, consumed by the dashboard and the terminal UI.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TrackerEvent {
+    GameDetected {
+        game_id: u64,
+    },
+    ReportLoaded {
+        report: Arc<GameReport>,
+    },
+    GameEnded {
+        game_id: u64,
+    },
+    RankUpdated {
+        snapshot: LpSnapshot,
+    },
+    /// Published after every check for a current game.
+    Polled {
+        in_game: bool,
+        rate_limits: Vec<RateLimitBudget>,
+        cache: CacheStats,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LpSnapshot {
+    /// Epoch milliseconds.
+    pub timestamp: i64,
+    pub tier: String,
+    pub rank: String,
+    pub league_points: i64,
+    pub wins: i64,
+    pub losses: i64,
+}
+
+impl LpSnapshot {
+    pub fn new(rank: &LeagueRank) -> Self {
+        LpSnapshot {
+            timestamp: now_millis(),
+            tier: rank.tier.clone(),
+            rank: rank.rank.clone(),
+            league_points: rank.league_points,
+            wins: rank.wins,
+            losses: rank.losses,
+        }
+    }
+
+    pub fn ladder_points(&self) -> Option<i64> {
+        ladder_points(&self.tier, &self.rank, self.league_points)
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::table::{premade_colour, team_colour, PLAYER_COLOUR};
+use league_notify::analysis::encounters;
+use league_notify::api::Api;
+use league_notify::ddragon::updater::DDragonUpdater;
+use league_notify::models::lol_match::MatchData;
+use league_notify::models::summoner::Summoner;
+use league_notify::report::GameReport;
+use league_notify::Result;
+
+const STYLE: &str = r#"
+body { background: #1e2328; color: #f0e6d2; font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #463714; padding: 0.4em 0.8em; text-align: left; vertical-align: middle; }
+th { background: #010a13; cursor: pointer; user-select: none; }
+th.sorted-asc::after { content: " \25B2"; }
+th.sorted-desc::after { content: " \25BC"; }
+img.icon { width: 32px; height: 32px; vertical-align: middle; margin-right: 0.4em; }
+.flagged { color: #e74c3c; }
+.off-role { color: #f1c40f; }
+"#;
+
+const SORT_SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable th").forEach((th, column) => {
+    th.addEventListener("click", () => {
+        const table = th.closest("table");
+        const body = table.tBodies[0];
+        const ascending = !th.classList.contains("sorted-asc");
+        const value = row => {
+            const cell = row.cells[column];
+            return cell.dataset.sort !== undefined ? cell.dataset.sort : cell.textContent.trim();
+        };
+
+        table.querySelectorAll("th").forEach(h => h.classList.remove("sorted-asc", "sorted-desc"));
+        th.classList.add(ascending ? "sorted-asc" : "sorted-desc");
+
+        Array.from(body.rows)
+            .sort((a, b) => {
+                const x = value(a), y = value(b);
+                const order = !isNaN(x) && !isNaN(y) && x !== "" && y !== ""
+                    ? Number(x) - Number(y)
+                    : x.localeCompare(y);
+                return ascending ? order : -order;
+            })
+            .forEach(row => body.appendChild(row));
+    });
+});
+"#;
+
+/// Writes `report` as a self-contained HTML page, with champion icons embedded from the Data
+/// Dragon cache. Once the game has finished, `result` adds the winning team and each player's KDA.
+pub async fn write_report(
+    api: &Api,
+    my_summoner: &Summoner,
+    report: &GameReport,
+    result: Option<&MatchData>,
+    path: &Path,
+) -> Result<()> {
+    let icons = champion_icons(api, report).await;
+    let icon = |champion_id: i64| {
+        icons
+            .get(&champion_id)
+            .map(|src| format!(r#"<img class="icon" src="{}">"#, src))
+            .unwrap_or_default()
+    };
+
+    let mut html = String::new();
+
+    write!(
+        html,
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>League Notifier - Game {}</title>
+<style>{}</style>
+</head>
+<body>
+<h1>{}</h1>
+"#,
+        report.game_id,
+        STYLE,
+        escape(&report.summary())
+    )
+    .expect("Failed to write HTML header");
+
+    html.push_str("<table>\n<tr><th>Team</th><th>Bans</th><th>Result</th></tr>\n");
+
+    for (team_id, team_name) in [(100, "Blue"), (200, "Red")] {
+        let bans = report
+            .bans
+            .iter()
+            .filter(|b| b.team_id == team_id)
+            .map(|b| format!("{}{}", icon(b.champion_id), escape(&b.champion_name)))
+            .collect::<Vec<_>>();
+
+        let team_result = result
+            .and_then(|m| m.info.teams.iter().find(|t| t.team_id == team_id))
+            .map_or("In Progress", |t| if t.win { "Victory" } else { "Defeat" });
+
+        writeln!(
+            html,
+            r#"<tr><td style="color: {}">{}</td><td>{}</td><td>{}</td></tr>"#,
+            css_colour(team_colour(team_id)),
+            team_name,
+            if bans.is_empty() {
+                "None".to_owned()
+            } else {
+                bans.join(" ")
+            },
+            team_result
+        )
+        .expect("Failed to write HTML bans");
+    }
+
+    html.push_str(
+        r#"</table>
+<table class="sortable">
+<thead><tr><th>Team</th><th>Summoner</th><th>Champion</th><th>Win Rate</th><th>Rank (Solo Queue)</th><th>Role</th><th>Spells</th><th>Runes</th><th>Premade</th><th>Met Before</th><th>Smurf?</th><th>KDA</th></tr></thead>
+<tbody>
+"#,
+    );
+
+    for cwr in report.players.iter() {
+        let name_colour = if cwr.summoner_name == my_summoner.summoner_info.name {
+            PLAYER_COLOUR
+        } else {
+            team_colour(cwr.team_id as i64)
+        };
+
+        let premade = match cwr.premade_group {
+            Some(group) => format!(
+                r#"<span style="color: {}">&#9632; {}</span>"#,
+                css_colour(premade_colour(group)),
+                group
+            ),
+            None => String::new(),
+        };
+
+        let runes = [cwr.keystone.as_deref(), cwr.secondary_tree.as_deref()]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        let kda = result
+            .and_then(|m| m.info.participants.iter().find(|p| p.puuid == cwr.puuid))
+            .map(|p| format!("{}/{}/{}", p.kills, p.deaths, p.assists))
+            .unwrap_or_default();
+
+        write!(
+            html,
+            r#"<tr>
+<td data-sort="{team}" style="color: {team_colour}">{team_name}</td>
+<td style="color: {name_colour}">{summoner}</td>
+<td>{icon}{champion}</td>
+<td data-sort="{win_rate}">{win_rate_string}</td>
+<td>{rank}</td>
+<td class="{role_class}">{role}</td>
+<td>{spells}</td>
+<td>{runes}</td>
+<td data-sort="{premade_group}">{premade}</td>
+<td>{met_before}</td>
+<td data-sort="{smurf_score}" class="{smurf_class}" title="{smurf_factors}">{smurf}</td>
+<td>{kda}</td>
+</tr>
+"#,
+            team = cwr.team_id,
+            team_colour = css_colour(team_colour(cwr.team_id as i64)),
+            team_name = if cwr.team_id == 100 { "Blue" } else { "Red" },
+            name_colour = css_colour(name_colour),
+            summoner = escape(&cwr.summoner_name),
+            icon = icon(cwr.champion_id),
+            champion = escape(&cwr.champion_name),
+            win_rate = if cwr.total_games > 0 {
+                cwr.win_rate as i32
+            } else {
+                -1
+            },
+            win_rate_string = escape(&cwr.win_rate_string()),
+            rank = escape(&cwr.rank),
+            role_class = if cwr.role.off_role { "off-role" } else { "" },
+            role = escape(&cwr.role.role_string()),
+            spells = escape(&cwr.spells.join(" / ")),
+            runes = escape(&runes.join(" / ")),
+            premade_group = cwr.premade_group.unwrap_or_default(),
+            premade = premade,
+            met_before = escape(&encounters::encounters_string(&cwr.encounters)),
+            smurf_score = cwr.smurf.score,
+            smurf_class = if cwr.smurf.flagged { "flagged" } else { "" },
+            smurf_factors = escape(&cwr.smurf.factors.join(", ")),
+            smurf = if cwr.smurf.flagged {
+                format!("&#9888; {}", cwr.smurf.score)
+            } else {
+                cwr.smurf.score.to_string()
+            },
+            kda = kda,
+        )
+        .expect("Failed to write HTML row");
+    }
+
+    write!(
+        html,
+        r#"</tbody>
+</table>
+<script>{}</script>
+</body>
+</html>
+"#,
+        SORT_SCRIPT
+    )
+    .expect("Failed to write HTML footer");
+
+    std::fs::write(path, html)?;
+
+    Ok(())
+}
+
+/// Champion icons of all picked and banned champions as data URIs, cropped from the sprite
+/// sheets. Champions whose icon can't be loaded are left out.
+async fn champion_icons(api: &Api, report: &GameReport) -> HashMap<i64, String> {
+    let static_data = api.static_data();
+    let mut icons = HashMap::new();
+
+    let ddragon =
+        DDragonUpdater::with_version(&static_data.version, api.locale(), api.retry_policy());
+
+    let champion_ids = report
+        .players
+        .iter()
+        .map(|p| p.champion_id)
+        .chain(report.bans.iter().map(|b| b.champion_id));
+
+    for champion_id in champion_ids {
+        if icons.contains_key(&champion_id) {
+            continue;
+        }
+
+        let champion = match static_data.champion(champion_id) {
+            Ok(champion) => champion,
+            Err(_) => continue,
+        };
+
+        match ddragon.sprite_image(&champion.image).await {
+            Ok(png) => {
+                icons.insert(
+                    champion_id,
+                    format!("data:image/png;base64,{}", base64::encode(png)),
+                );
+            }
+            Err(e) => error!("Could not load icon for {}: {}", champion.name, e),
+        }
+    }
+
+    icons
+}
+
+fn css_colour(colour: comfy_table::Color) -> String {
+    match colour {
+        comfy_table::Color::Rgb { r, g, b } => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        _ => "inherit".to_owned(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
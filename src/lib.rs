@@ -0,0 +1,27 @@
+//! Riot API client and game analysis used by the League Notifier command line tool.
+
+#[macro_use]
+extern crate derive_builder;
+#[macro_use]
+extern crate tracing;
+
+pub mod analysis;
+pub mod api;
+pub mod columns;
+pub mod ddragon;
+pub mod endpoints;
+pub mod events;
+pub mod metrics;
+pub mod models;
+pub mod report;
+pub mod response_cache;
+pub mod retry;
+pub mod session;
+pub mod util;
+
+pub use api::{Api, ApiRegion, Priority};
+pub use ddragon::updater::DDragonUpdater;
+pub use models::error::MyError;
+pub use retry::RetryPolicy;
+
+pub type Result<T> = std::result::Result<T, MyError>;
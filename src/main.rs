@@ -1,37 +1,54 @@
 #[macro_use]
-extern crate derive_builder;
-#[macro_use]
-extern crate log;
+extern crate tracing;
 
-use std::collections::HashSet;
-use std::env;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use std::{future, io, process};
 
 use anyhow::anyhow;
-use clap::{App, Arg};
-use models::error::MyError;
+use clap::{App, AppSettings, Arg, ArgMatches};
 use strum::VariantNames;
+use tokio::signal;
+use tokio::sync::{broadcast, watch, RwLock};
+use tokio::task::JoinHandle;
 use tokio::time::Duration;
+use tracing::{instrument, Instrument};
+use tracing_subscriber::EnvFilter;
 
-use crate::api::{Api, ApiRegion};
-use crate::endpoints::summoner;
-use crate::util::StringExt;
+use league_notify::analysis::smurf::SmurfConfig;
+use league_notify::columns::{Column, SortKey, DEFAULT_COLUMNS, DEFAULT_SORT};
+use league_notify::ddragon::cache::DDragonCache;
+use league_notify::endpoints::ddragon as ddragon_endpoint;
+use league_notify::endpoints::summoner;
+use league_notify::events::{LpSnapshot, TrackerEvent};
+use league_notify::metrics;
+use league_notify::models::lol_match::MatchHistory;
+use league_notify::models::summoner::{CurrentGameInfo, Summoner};
+use league_notify::report::{self, GameReport};
+use league_notify::session::{self, SessionState, SessionSummary};
+use league_notify::{Api, ApiRegion, DDragonUpdater, MyError, Priority, Result, RetryPolicy};
 
-mod api;
-mod ddragon;
-mod endpoints;
-mod models;
+mod dashboard;
+mod html;
+#[cfg(all(feature = "cli", feature = "metrics"))]
+mod metrics_server;
 mod table;
-mod util;
+mod tui;
 
-type Result<T> = std::result::Result<T, MyError>;
+/// Number of the tracked summoner's recent matches searched for previous encounters.
+const HISTORY_MATCH_COUNT: u8 = 50;
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    env::set_var("RUST_LOG", "INFO");
+/// Times the results of a finished game are fetched again while match-v5 doesn't have them yet.
+const FINISHED_MATCH_ATTEMPTS: u32 = 10;
 
-    pretty_env_logger::init_timed();
+/// Delay between attempts to fetch the results of a finished game.
+const FINISHED_MATCH_RETRY_DELAY: Duration = Duration::from_secs(30);
 
-    let matches = App::new("League Notifier")
+#[tokio::main]
+async fn main() -> Result<()> {
+    let app = App::new("League Notifier")
         .version("1.0")
         .author("Zak")
         .about("Gives you info about current players in your game.")
@@ -60,7 +77,129 @@ async fn main() -> Result<()> {
                 .takes_value(true)
                 .required(true),
         )
-        .get_matches();
+        .arg(
+            Arg::new("json")
+                .short('j')
+                .long("json")
+                .help("Output game reports as JSON instead of a table"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Show additional details such as smurf score factors"),
+        )
+        .arg(
+            Arg::new("smurf-threshold")
+                .long("smurf-threshold")
+                .help("Smurf score (0-100) at which a player is flagged")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("smurf-config")
+                .long("smurf-config")
+                .help("JSON file with the weights and limits of the smurf score")
+                .takes_value(true)
+                .value_name("path"),
+        )
+        .arg(
+            Arg::new("columns")
+                .long("columns")
+                .help("Comma separated columns to show in the table")
+                .takes_value(true)
+                .use_delimiter(true)
+                .possible_values(Column::VARIANTS),
+        )
+        .arg(
+            Arg::new("sort")
+                .long("sort")
+                .help("Comma separated columns to sort each team by, e.g. rank:desc,level:asc")
+                .takes_value(true)
+                .use_delimiter(true),
+        )
+        .arg(
+            Arg::new("html")
+                .long("html")
+                .help("Also write reports of live and finished games to a self-contained HTML file")
+                .takes_value(true)
+                .value_name("path"),
+        )
+        .arg(
+            Arg::new("serve")
+                .long("serve")
+                .help("Serve a live dashboard on the given address, e.g. 0.0.0.0:8080")
+                .takes_value(true)
+                .value_name("address"),
+        )
+        .arg(
+            Arg::new("tui")
+                .short('t')
+                .long("tui")
+                .help("Show games in an interactive full-screen terminal UI, logging to a file")
+                .conflicts_with("json"),
+        )
+        .arg(
+            Arg::new("locale")
+                .short('l')
+                .long("locale")
+                .help("Language of champion, item, spell and rune names (e.g. ko_KR, de_DE)")
+                .takes_value(true)
+                .default_value(ddragon_endpoint::DEFAULT_LOCALE)
+                .global(true),
+        )
+        .arg(
+            Arg::new("log-format")
+                .long("log-format")
+                .help("Format of log lines, filtered with RUST_LOG (default: info)")
+                .takes_value(true)
+                .possible_values(["text", "json"])
+                .default_value("text")
+                .global(true),
+        )
+        .setting(AppSettings::SubcommandsNegateReqs)
+        .subcommand(
+            App::new("ddragon")
+                .about("Manage the local Data Dragon cache")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(App::new("update").about("Download the latest static data"))
+                .subcommand(App::new("list").about("List cached versions"))
+                .subcommand(
+                    App::new("prune").about("Remove old cached versions").arg(
+                        Arg::new("keep")
+                            .long("keep")
+                            .help("Number of versions to keep")
+                            .takes_value(true)
+                            .default_value("1"),
+                    ),
+                ),
+        );
+
+    #[cfg(all(feature = "cli", feature = "metrics"))]
+    let app = app.arg(
+        Arg::new("metrics")
+            .long("metrics")
+            .help("Serve Prometheus metrics on /metrics at the given address, e.g. 0.0.0.0:9090")
+            .takes_value(true)
+            .value_name("address"),
+    );
+
+    let matches = app.get_matches();
+
+    let json_logs = matches.value_of("log-format") == Some("json");
+
+    // Log lines would be drawn over the terminal UI, so they go to a file instead.
+    if matches.is_present("tui") {
+        let log_path = session::log_path()?;
+
+        init_file_logging(&log_path, json_logs)?;
+        eprintln!("Logging to {}", log_path.display());
+    } else {
+        init_logging(json_logs);
+    }
+
+    if let Some(ddragon_matches) = matches.subcommand_matches("ddragon") {
+        return ddragon_command(ddragon_matches).await;
+    }
 
     let api_key = matches.value_of("key").expect("Missing API Key");
 
@@ -72,82 +211,588 @@ async fn main() -> Result<()> {
 
     let summoner_name = matches.value_of("name").expect("Missing Summoner name");
 
-    tokio::task::block_in_place(|| track_summoner(api_key, region, summoner_name)).await?;
+    let mut smurf_config = match matches.value_of("smurf-config") {
+        Some(path) => SmurfConfig::from_file(Path::new(path))?,
+        None => SmurfConfig::default(),
+    };
+
+    if let Some(threshold) = matches.value_of("smurf-threshold") {
+        smurf_config.threshold = threshold
+            .parse()
+            .ok()
+            .filter(|threshold| *threshold <= 100)
+            .ok_or_else(|| anyhow!("Smurf threshold must be between 0 and 100"))?;
+    }
+
+    let mut columns = match matches.values_of("columns") {
+        Some(values) => values
+            .map(|c| c.parse().expect("Invalid column"))
+            .collect::<Vec<_>>(),
+        None => DEFAULT_COLUMNS.to_vec(),
+    };
+
+    if matches.is_present("verbose") && !columns.contains(&Column::SmurfFactors) {
+        columns.push(Column::SmurfFactors);
+    }
+
+    let sort_keys = match matches.values_of("sort") {
+        Some(values) => values
+            .map(|k| k.parse())
+            .collect::<Result<Vec<SortKey>>>()?,
+        None => DEFAULT_SORT.to_vec(),
+    };
+
+    let options = TrackOptions {
+        output_json: matches.is_present("json"),
+        tui: matches.is_present("tui"),
+        verbose: matches.is_present("verbose"),
+        smurf_config,
+        columns,
+        sort_keys,
+        locale: matches
+            .value_of("locale")
+            .expect("Missing locale")
+            .to_owned(),
+        html_path: matches.value_of("html").map(PathBuf::from),
+    };
+
+    let (events, _) = broadcast::channel(16);
+
+    if let Some(address) = matches.value_of("serve") {
+        let address = address
+            .parse()
+            .map_err(|_| anyhow!("Invalid dashboard address"))?;
+
+        let dashboard_events = events.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = dashboard::serve(address, dashboard_events).await {
+                error!("{}", e);
+            }
+        });
+    }
+
+    #[cfg(all(feature = "cli", feature = "metrics"))]
+    if let Some(address) = matches.value_of("metrics") {
+        let address = address
+            .parse()
+            .map_err(|_| anyhow!("Invalid metrics address"))?;
+
+        tokio::spawn(async move {
+            if let Err(e) = metrics_server::serve(address).await {
+                error!("{}", e);
+            }
+        });
+    }
+
+    // Set on Ctrl-C, SIGTERM or when quitting the terminal UI to stop tracking and save the session.
+    let (shutdown, shutdown_receiver) = watch::channel(false);
+    let shutdown = Arc::new(shutdown);
+
+    tokio::spawn({
+        let shutdown = Arc::clone(&shutdown);
+        let tui = options.tui;
+
+        async move {
+            shutdown_signal().await;
+
+            info!("Shutting down - Press Ctrl-C again to exit immediately.");
+            shutdown.send_replace(true);
+
+            shutdown_signal().await;
+
+            // Exiting skips destructors, including the one restoring the terminal.
+            if tui {
+                tui::restore_terminal();
+            }
+
+            process::exit(130);
+        }
+    });
+
+    let tracker = tokio::task::block_in_place(|| {
+        track_summoner(
+            api_key,
+            region,
+            summoner_name,
+            &options,
+            &events,
+            shutdown_receiver,
+        )
+    });
+
+    let summary = if options.tui {
+        tui::run(
+            summoner_name,
+            &options.columns,
+            options.verbose,
+            events.subscribe(),
+            tracker,
+            &shutdown,
+        )
+        .await?
+    } else {
+        tracker.await?
+    };
+
+    if let Some(summary) = summary {
+        println!("{}", summary);
+    }
+
+    Ok(())
+}
+
+/// Logs to stderr, so that JSON reports on stdout can be piped.
+fn init_logging(json: bool) {
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(log_filter())
+        .with_writer(io::stderr);
+
+    if json {
+        subscriber.json().init();
+    } else {
+        subscriber.init();
+    }
+}
+
+/// Appends log lines to `path`, for when the terminal is taken by the UI.
+fn init_file_logging(path: &Path, json: bool) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(log_filter())
+        .with_ansi(false)
+        .with_writer(Mutex::new(file));
+
+    if json {
+        subscriber.json().init();
+    } else {
+        subscriber.init();
+    }
+
+    Ok(())
+}
+
+fn log_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"))
+}
+
+async fn ddragon_command(matches: &ArgMatches) -> Result<()> {
+    match matches.subcommand() {
+        Some(("update", update_matches)) => {
+            let locale = update_matches.value_of("locale").expect("Missing locale");
+
+            let ddragon = DDragonUpdater::new(locale, &RetryPolicy::default()).await?;
+
+            ddragon.download_static_data().await?;
+
+            println!(
+                "Static data is up to date with version {} ({}).",
+                ddragon.version(),
+                ddragon.locale()
+            );
+        }
+        Some(("list", _)) => {
+            let cache = DDragonCache::new()?;
+
+            for version in cache.versions()? {
+                let locales = cache.locales(&version)?;
+
+                if locales.is_empty() {
+                    println!("{} (incomplete)", version);
+                } else {
+                    println!("{} ({})", version, locales.join(", "));
+                }
+            }
+        }
+        Some(("prune", prune_matches)) => {
+            let keep = prune_matches
+                .value_of("keep")
+                .expect("Missing keep count")
+                .parse()
+                .map_err(|_| anyhow!("Invalid keep count"))?;
+
+            for version in DDragonCache::new()?.prune(keep)? {
+                println!("Removed {}", version);
+            }
+        }
+        _ => unreachable!(),
+    }
+
     Ok(())
 }
 
-pub async fn track_summoner(api_key: &str, region: ApiRegion, summoner_name: &str) -> Result<()> {
-    let api = Api::new(api_key, region).await?;
+#[derive(Debug)]
+pub struct TrackOptions {
+    pub output_json: bool,
+    /// Reports are only shown in the terminal UI, not printed.
+    pub tui: bool,
+    pub verbose: bool,
+    pub smurf_config: SmurfConfig,
+    pub columns: Vec<Column>,
+    pub sort_keys: Vec<SortKey>,
+    pub locale: String,
+    pub html_path: Option<PathBuf>,
+}
+
+async fn publish_rank(
+    summoner: &Summoner,
+    events: &broadcast::Sender<TrackerEvent>,
+) -> Option<LpSnapshot> {
+    match summoner.fresh_solo_queue_rank().await {
+        Ok(rank) => {
+            let snapshot = LpSnapshot::new(&rank);
+
+            let _ = events.send(TrackerEvent::RankUpdated {
+                snapshot: snapshot.clone(),
+            });
+
+            Some(snapshot)
+        }
+        Err(e) => {
+            info!("Could not get rank: {}", e);
+            None
+        }
+    }
+}
+
+/// Completes on Ctrl-C, or SIGTERM on Unix.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = signal::ctrl_c().await {
+            error!("Could not listen for Ctrl-C: {}", e);
+            future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                error!("Could not listen for SIGTERM: {}", e);
+                future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Completes once `shutdown` is set.
+async fn shutdown_requested(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if shutdown.changed().await.is_err() {
+            future::pending::<()>().await;
+        }
+    }
+}
+
+/// Tracks the summoner until `shutdown` is set, then saves the session state and returns a summary
+/// of the session. Returns `None` if the summoner could not be found.
+#[instrument(name = "summoner", skip_all, fields(name = summoner_name, region = %region))]
+pub async fn track_summoner(
+    api_key: &str,
+    region: ApiRegion,
+    summoner_name: &str,
+    options: &TrackOptions,
+    events: &broadcast::Sender<TrackerEvent>,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<Option<SessionSummary>> {
+    let started = Instant::now();
+    let state_path = SessionState::path(&region, summoner_name)?;
+    let response_cache_path = session::response_cache_path(&region)?;
+
+    let api = Api::new(api_key, region, &options.locale, RetryPolicy::default()).await?;
 
-    match api
+    if let Err(e) = api.response_cache().load(&response_cache_path) {
+        warn!("Could not load the saved response cache: {}", e);
+    }
+
+    let my_summoner = match api
         .get_summoner(summoner::SummonerEndpointBy::Name(summoner_name))
         .await
     {
-        Ok(my_summoner) => {
-            let mut games_notified = HashSet::new();
-
-            loop {
-                match my_summoner.current_game_info().await {
-                    Ok(cgi) => {
-                        let game_notified_id =
-                            format!("{}-{}", &my_summoner.summoner_info.name, cgi.game_id);
-
-                        if !games_notified.contains(&game_notified_id) {
-                            info!("Game detected, loading info...");
-
-                            let mut results = Vec::with_capacity(10);
-
-                            for summoner_current_game_info in cgi.summoners.iter() {
-                                let summoner = &summoner_current_game_info.summoner;
-
-                                if let Ok(mut cwr) = summoner
-                                    .champion_win_rate(summoner_current_game_info.champion_id)
-                                    .await
-                                {
-                                    cwr.team_id = summoner_current_game_info.team_id;
-                                    cwr.summoner_name = summoner.summoner_info.name.clone();
-
-                                    if let Ok(rank) = summoner.solo_queue_rank().await {
-                                        cwr.rank = format!(
-                                            "{} {}",
-                                            rank.tier.to_owned().title_case(),
-                                            rank.rank
-                                        );
-                                    };
-
-                                    results.push(cwr);
-                                } else {
-                                    info!(
-                                        "Couldn't find win rate for summoner: {}",
-                                        &summoner.summoner_info.name
-                                    );
-                                }
-                            }
-
-                            results.sort();
-
-                            let table = table::generate_table(&my_summoner, results);
-
-                            info!("\n\n{}\n", table);
-
-                            games_notified.insert(game_notified_id);
+        // Polling and game reports go ahead of the match history sync.
+        Ok(my_summoner) => my_summoner.with_priority(Priority::LiveGame),
+        Err(e) if e.is_fatal() => return Err(e),
+        Err(e) => {
+            error!("Could not get summoner: {}", e);
+            return Ok(None);
+        }
+    };
+
+    let mut state = SessionState::load(&state_path).unwrap_or_else(|e| {
+        warn!("Could not load the previous session: {}", e);
+        SessionState::default()
+    });
+
+    let history = Arc::new(RwLock::new(MatchHistory::default()));
+    let mut backfill = spawn_backfill(&my_summoner, &history);
+    let mut current_game_id = None;
+    let mut games_tracked = 0;
+    let mut lp_snapshots = 0;
+
+    if let Some(snapshot) = publish_rank(&my_summoner, events).await {
+        state.push_lp_snapshot(snapshot);
+        lp_snapshots += 1;
+    }
+
+    let result = loop {
+        let poll = async {
+            match my_summoner.current_game_info().await {
+                Ok(cgi) => {
+                    let new_game = current_game_id != Some(cgi.game_id);
+                    current_game_id = Some(cgi.game_id);
+
+                    if !state.is_notified(cgi.game_id) {
+                        let report =
+                            report_game(&api, &my_summoner, &cgi, &history, options, events)
+                                .await?;
+
+                        // Picks up the matches played since the last sync for the next report.
+                        backfill.abort();
+                        backfill = spawn_backfill(&my_summoner, &history);
+
+                        state.mark_notified(report);
+                        games_tracked += 1;
+
+                        // Saved right away so that a restart mid-game doesn't notify it again.
+                        if let Err(e) = state.save(&state_path) {
+                            error!("Could not save the session: {}", e);
                         }
+                    } else if let Some(report) = state.report(cgi.game_id).filter(|_| new_game) {
+                        info!(
+                            game_id = cgi.game_id,
+                            "Game already notified, showing the saved report."
+                        );
+
+                        let _ = events.send(TrackerEvent::GameDetected {
+                            game_id: cgi.game_id,
+                        });
+
+                        show_report(&my_summoner, Arc::clone(report), options, events)?;
                     }
-                    Err(e) => match e {
-                        MyError::Reqwest(_) | MyError::Serde(_) | MyError::Other(_) => {
-                            error!("{}", e)
-                        }
-                        _ => (),
-                    },
                 }
+                Err(MyError::NotInGame) => {
+                    if let Some(game_id) = current_game_id.take() {
+                        info!(game_id, "Game finished.");
+
+                        let _ = events.send(TrackerEvent::GameEnded { game_id });
 
-                tokio::time::sleep(Duration::from_secs(30)).await;
+                        if let (Some(html_path), Some(report)) =
+                            (&options.html_path, state.report(game_id))
+                        {
+                            spawn_finished_report(&api, &my_summoner, game_id, report, html_path);
+                        }
+
+                        if let Some(snapshot) = publish_rank(&my_summoner, events).await {
+                            state.push_lp_snapshot(snapshot);
+                            lp_snapshots += 1;
+                        }
+                    }
+                }
+                Err(e) if e.is_fatal() => {
+                    error!("Stopping tracking: {}", e);
+                    return Err(e);
+                }
+                Err(
+                    e @ (MyError::RateLimited { .. }
+                    | MyError::ServiceUnavailable { .. }
+                    | MyError::Timeout { .. }),
+                ) => warn!("{} - Retrying on the next poll.", e),
+                Err(e) => error!("{}", e),
             }
+
+            let _ = events.send(TrackerEvent::Polled {
+                in_game: current_game_id.is_some(),
+                rate_limits: api.rate_limit_budget(),
+                cache: api.cache_stats(),
+            });
+
+            Ok(())
+        };
+
+        // Shutting down cancels requests still in flight. A game whose report was interrupted
+        // isn't marked as notified, so it is reported again in the next session.
+        tokio::select! {
+            result = poll => if let Err(e) = result {
+                break Err(e);
+            },
+            _ = shutdown_requested(&mut shutdown) => break Ok(()),
         }
-        Err(e) => {
-            error!("Could not get summoner: {}", e);
+
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(30)) => {}
+            _ = shutdown_requested(&mut shutdown) => break Ok(()),
         }
     };
 
+    backfill.abort();
+
+    info!("Saving session...");
+
+    if let Err(e) = state.save(&state_path) {
+        error!("Could not save the session: {}", e);
+    }
+
+    if let Err(e) = api.response_cache().save(&response_cache_path) {
+        error!("Could not save the response cache: {}", e);
+    }
+
+    result?;
+
+    let session_snapshots =
+        &state.lp_history[state.lp_history.len().saturating_sub(lp_snapshots)..];
+
+    Ok(Some(SessionSummary {
+        duration: started.elapsed(),
+        games_tracked,
+        lp_change: SessionSummary::lp_change(session_snapshots),
+        requests_sent: api.requests_sent(),
+    }))
+}
+
+/// Syncs the match history in the background, with requests queued behind polling and reports.
+fn spawn_backfill(my_summoner: &Summoner, history: &Arc<RwLock<MatchHistory>>) -> JoinHandle<()> {
+    let my_summoner = my_summoner.with_priority(Priority::Background);
+    let history = Arc::clone(history);
+
+    tokio::spawn(
+        async move {
+            if let Err(e) = my_summoner
+                .refresh_match_history(&history, HISTORY_MATCH_COUNT)
+                .await
+            {
+                error!("Could not refresh match history: {}", e);
+            }
+        }
+        .in_current_span(),
+    )
+}
+
+/// Rewrites the HTML report of a finished game with its results, once match-v5 has them.
+fn spawn_finished_report(
+    api: &Api,
+    my_summoner: &Summoner,
+    game_id: u64,
+    report: &Arc<GameReport>,
+    html_path: &Path,
+) {
+    let api = api.clone();
+    let my_summoner = my_summoner.clone();
+    let report = Arc::clone(report);
+    let html_path = html_path.to_owned();
+
+    tokio::spawn(
+        async move {
+            let game_match = my_summoner.game_match(game_id);
+            let mut attempts = 0;
+
+            // Matches only show up in match-v5 a little while after the game ends.
+            let match_data = loop {
+                match game_match.match_data().await {
+                    Ok(match_data) => break match_data,
+                    Err(MyError::NotFound { .. }) if attempts < FINISHED_MATCH_ATTEMPTS => {
+                        attempts += 1;
+                        tokio::time::sleep(FINISHED_MATCH_RETRY_DELAY).await;
+                    }
+                    Err(e) => {
+                        error!("Could not get the results of the game: {}", e);
+                        return;
+                    }
+                }
+            };
+
+            match html::write_report(&api, &my_summoner, &report, Some(&match_data), &html_path)
+                .await
+            {
+                Ok(()) => info!(
+                    "HTML report with results written to {}",
+                    html_path.display()
+                ),
+                Err(e) => error!("Could not write HTML report: {}", e),
+            }
+        }
+        .instrument(info_span!("game", game_id)),
+    );
+}
+
+/// Loads and outputs the report of a newly detected game.
+#[instrument(name = "game", skip_all, fields(game_id = cgi.game_id))]
+async fn report_game(
+    api: &Api,
+    my_summoner: &Summoner,
+    cgi: &CurrentGameInfo,
+    history: &RwLock<MatchHistory>,
+    options: &TrackOptions,
+    events: &broadcast::Sender<TrackerEvent>,
+) -> Result<Arc<GameReport>> {
+    info!("Game detected, loading info...");
+
+    metrics::global().record_game_detected();
+
+    let _ = events.send(TrackerEvent::GameDetected {
+        game_id: cgi.game_id,
+    });
+
+    let report = report::game_report(
+        api,
+        my_summoner,
+        cgi,
+        &*history.read().await,
+        &options.smurf_config,
+        &options.sort_keys,
+    )
+    .await;
+
+    let report = Arc::new(report);
+
+    show_report(my_summoner, Arc::clone(&report), options, events)?;
+
+    if let Some(html_path) = &options.html_path {
+        match html::write_report(api, my_summoner, &report, None, html_path).await {
+            Ok(()) => info!("HTML report written to {}", html_path.display()),
+            Err(e) => error!("Could not write HTML report: {}", e),
+        }
+    }
+
+    Ok(report)
+}
+
+/// Outputs a game report and publishes it to the dashboard and terminal UI.
+fn show_report(
+    my_summoner: &Summoner,
+    report: Arc<GameReport>,
+    options: &TrackOptions,
+    events: &broadcast::Sender<TrackerEvent>,
+) -> Result<()> {
+    if options.output_json {
+        println!("{}", serde_json::to_string_pretty(&*report)?);
+    } else if !options.tui {
+        let table = table::generate_table(my_summoner, &report, &options.columns, options.verbose);
+
+        println!(
+            "\n{}\n{}\n\n{}\n",
+            report.summary(),
+            table::generate_bans_table(&report),
+            table
+        );
+    }
+
+    let _ = events.send(TrackerEvent::ReportLoaded { report });
+
     Ok(())
 }
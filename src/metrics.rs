@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::response_cache::CacheStats;
+
+/// Upper bounds in seconds of the limiter wait time histogram buckets.
+const WAIT_BUCKETS: [f64; 10] = [0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 5.0, 15.0, 60.0, 120.0];
+
+/// Recording takes a lock per request, so builds without the `metrics` feature skip it.
+const ENABLED: bool = cfg!(feature = "metrics");
+
+static METRICS: Metrics = Metrics::new();
+
+/// Metrics recorded by every `Api` in the process.
+pub fn global() -> &'static Metrics {
+    &METRICS
+}
+
+/// Counters and histograms of the requests made to the Riot API, rendered in the Prometheus text
+/// format by `render`.
+#[derive(Debug)]
+pub struct Metrics {
+    /// Keyed by endpoint name and response status.
+    requests: Mutex<BTreeMap<(&'static str, String), u64>>,
+    /// Keyed by the rate limit type Riot reports.
+    rate_limited: Mutex<BTreeMap<String, u64>>,
+    retries: AtomicU64,
+    limiter_wait: Histogram,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    cache_coalesced: AtomicU64,
+    games_detected: AtomicU64,
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Metrics {
+            requests: Mutex::new(BTreeMap::new()),
+            rate_limited: Mutex::new(BTreeMap::new()),
+            retries: AtomicU64::new(0),
+            limiter_wait: Histogram::new(),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            cache_coalesced: AtomicU64::new(0),
+            games_detected: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a request to `endpoint`, `status` is the response status code or the kind of
+    /// error if no response was received.
+    pub fn record_request(&self, endpoint: &'static str, status: &str) {
+        if !ENABLED {
+            return;
+        }
+
+        *self
+            .requests
+            .lock()
+            .expect("Request metrics lock poisoned")
+            .entry((endpoint, status.to_owned()))
+            .or_default() += 1;
+    }
+
+    pub fn record_rate_limited(&self, scope: &str) {
+        if !ENABLED {
+            return;
+        }
+
+        *self
+            .rate_limited
+            .lock()
+            .expect("Rate limit metrics lock poisoned")
+            .entry(scope.to_owned())
+            .or_default() += 1;
+    }
+
+    pub fn record_retry(&self) {
+        if !ENABLED {
+            return;
+        }
+
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_limiter_wait(&self, wait: Duration) {
+        if !ENABLED {
+            return;
+        }
+
+        self.limiter_wait.observe(wait);
+    }
+
+    pub fn record_cache_hit(&self) {
+        if !ENABLED {
+            return;
+        }
+
+        self.cache_hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_miss(&self) {
+        if !ENABLED {
+            return;
+        }
+
+        self.cache_misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_cache_coalesced(&self) {
+        if !ENABLED {
+            return;
+        }
+
+        self.cache_coalesced.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_game_detected(&self) {
+        if !ENABLED {
+            return;
+        }
+
+        self.games_detected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// All metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "league_notify_requests_total",
+            "counter",
+            "Riot API requests by endpoint and response status.",
+        );
+
+        for ((endpoint, status), count) in self
+            .requests
+            .lock()
+            .expect("Request metrics lock poisoned")
+            .iter()
+        {
+            let _ = writeln!(
+                out,
+                "league_notify_requests_total{{endpoint=\"{}\",status=\"{}\"}} {}",
+                endpoint, status, count
+            );
+        }
+
+        header(
+            &mut out,
+            "league_notify_rate_limited_total",
+            "counter",
+            "Responses with status 429 by rate limit type.",
+        );
+
+        for (scope, count) in self
+            .rate_limited
+            .lock()
+            .expect("Rate limit metrics lock poisoned")
+            .iter()
+        {
+            let _ = writeln!(
+                out,
+                "league_notify_rate_limited_total{{scope=\"{}\"}} {}",
+                escape(scope),
+                count
+            );
+        }
+
+        counter(
+            &mut out,
+            "league_notify_retries_total",
+            "Requests retried after a failed attempt.",
+            self.retries.load(Ordering::Relaxed),
+        );
+
+        self.limiter_wait.render(
+            &mut out,
+            "league_notify_limiter_wait_seconds",
+            "Time requests waited for rate limit budget.",
+        );
+
+        let hits = self.cache_hits.load(Ordering::Relaxed);
+        let misses = self.cache_misses.load(Ordering::Relaxed);
+        let coalesced = self.cache_coalesced.load(Ordering::Relaxed);
+
+        header(
+            &mut out,
+            "league_notify_cache_requests_total",
+            "counter",
+            "Response cache lookups by result.",
+        );
+
+        for (result, count) in [("hit", hits), ("miss", misses), ("coalesced", coalesced)] {
+            let _ = writeln!(
+                out,
+                "league_notify_cache_requests_total{{result=\"{}\"}} {}",
+                result, count
+            );
+        }
+
+        let hit_ratio = CacheStats {
+            hits,
+            misses,
+            coalesced,
+        }
+        .hit_ratio();
+
+        header(
+            &mut out,
+            "league_notify_cache_hit_ratio",
+            "gauge",
+            "Share of responses not requested from the API.",
+        );
+        let _ = writeln!(out, "league_notify_cache_hit_ratio {}", hit_ratio);
+
+        counter(
+            &mut out,
+            "league_notify_games_detected_total",
+            "Games of the tracked summoner detected.",
+            self.games_detected.load(Ordering::Relaxed),
+        );
+
+        out
+    }
+}
+
+#[derive(Debug)]
+struct Histogram {
+    /// Observations per bucket of `WAIT_BUCKETS`, not cumulative.
+    buckets: [AtomicU64; WAIT_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    const fn new() -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: AtomicU64 = AtomicU64::new(0);
+
+        Histogram {
+            buckets: [ZERO; WAIT_BUCKETS.len()],
+            count: AtomicU64::new(0),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value: Duration) {
+        let seconds = value.as_secs_f64();
+
+        if let Some(bucket) = WAIT_BUCKETS.iter().position(|le| seconds <= *le) {
+            self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add(value.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        header(out, name, "histogram", help);
+
+        let mut cumulative = 0;
+
+        for (le, bucket) in WAIT_BUCKETS.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, le, cumulative);
+        }
+
+        let count = self.count.load(Ordering::Relaxed);
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, count);
+        let _ = writeln!(out, "{}_sum {}", name, sum);
+        let _ = writeln!(out, "{}_count {}", name, count);
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: u64) {
+    header(out, name, "counter", help);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Escapes a label value received from the API.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
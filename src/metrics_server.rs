@@ -0,0 +1,43 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use anyhow::Context;
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+
+use league_notify::metrics;
+use league_notify::Result;
+
+/// Serves the metrics of the session on `addr` until the server fails.
+pub async fn serve(addr: SocketAddr) -> Result<()> {
+    let make_service = make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(handle)) });
+
+    info!("Metrics available on http://{}/metrics", addr);
+
+    Server::try_bind(&addr)
+        .context("Could not bind metrics address")?
+        .serve(make_service)
+        .await
+        .context("Metrics server failed")?;
+
+    Ok(())
+}
+
+async fn handle(req: Request<Body>) -> std::result::Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET || req.uri().path() != "/metrics" {
+        let mut response = Response::new(Body::from(StatusCode::NOT_FOUND.to_string()));
+        *response.status_mut() = StatusCode::NOT_FOUND;
+
+        return Ok(response);
+    }
+
+    let mut response = Response::new(Body::from(metrics::global().render()));
+
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("text/plain; version=0.0.4"),
+    );
+
+    Ok(response)
+}
@@ -1,6 +1,12 @@
-use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::analysis::encounters::Encounter;
+use crate::analysis::roles::RoleInfo;
+use crate::analysis::smurf::SmurfScore;
+use crate::models::champion_mastery::ChampionMastery;
+use crate::models::leagues::LeagueRank;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct ChampionWinRate {
     pub champion_id: i64,
     pub champion_name: String,
@@ -8,28 +14,39 @@ pub struct ChampionWinRate {
     pub win_rate: u16,
     pub team_id: u8,
     pub rank: String,
+    pub solo_queue_rank: Option<LeagueRank>,
     pub summoner_name: String,
+    pub puuid: String,
+    pub premade_group: Option<u8>,
+    pub encounters: Vec<Encounter>,
+    pub role: RoleInfo,
+    pub summoner_level: i64,
+    pub smurf: SmurfScore,
+    pub spells: Vec<String>,
+    pub keystone: Option<String>,
+    pub secondary_tree: Option<String>,
+    /// Mastery of the current champion, `None` if it has never been played.
+    pub mastery: Option<ChampionMastery>,
+    /// Recent matches on any champion, most recent first.
+    pub recent_matches: Vec<RecentMatch>,
 }
 
-impl std::cmp::Ord for ChampionWinRate {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.team_id
-            .cmp(&other.team_id)
-            .then(self.win_rate.cmp(&other.win_rate).reverse())
-    }
-}
-
-impl std::cmp::PartialOrd for ChampionWinRate {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentMatch {
+    pub match_id: String,
+    pub game_creation: i64,
+    pub queue_id: i64,
+    pub champion_id: i64,
+    pub champion_name: String,
+    pub kills: i64,
+    pub deaths: i64,
+    pub assists: i64,
+    pub won: bool,
 }
 
-impl std::cmp::Eq for ChampionWinRate {}
-
-impl std::cmp::PartialEq for ChampionWinRate {
-    fn eq(&self, other: &Self) -> bool {
-        self.champion_id == other.champion_id
+impl RecentMatch {
+    pub fn kda_string(&self) -> String {
+        format!("{}/{}/{}", self.kills, self.deaths, self.assists)
     }
 }
 
@@ -47,10 +64,38 @@ impl ChampionWinRate {
             total_games: wins + losses,
             team_id: 0,
             rank: "Unranked".to_owned(),
+            solo_queue_rank: None,
             summoner_name: "Unknown".to_owned(),
+            puuid: String::new(),
+            premade_group: None,
+            encounters: Vec::new(),
+            role: RoleInfo::default(),
+            summoner_level: 0,
+            smurf: SmurfScore::default(),
+            spells: Vec::new(),
+            keystone: None,
+            secondary_tree: None,
+            mastery: None,
+            recent_matches: Vec::new(),
         }
     }
 
+    /// Solo queue win rate over the whole season.
+    pub fn overall_win_rate(&self) -> Option<u16> {
+        let rank = self.solo_queue_rank.as_ref()?;
+        let games = rank.wins + rank.losses;
+
+        if games == 0 {
+            return None;
+        }
+
+        Some((rank.wins * 100 / games) as u16)
+    }
+
+    pub fn league_points(&self) -> Option<i64> {
+        self.solo_queue_rank.as_ref().map(|r| r.league_points)
+    }
+
     pub fn win_rate_string(&self) -> String {
         if self.total_games > 0 {
             format!(
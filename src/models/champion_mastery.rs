@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionMastery {
+    pub champion_id: i64,
+    pub champion_level: i64,
+    pub champion_points: i64,
+    /// Epoch milliseconds.
+    pub last_play_time: i64,
+}
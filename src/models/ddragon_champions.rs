@@ -2,36 +2,103 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::error::MyError;
+use crate::Result;
+
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(from = "RawChampionData")]
 pub struct ChampionData {
     #[serde(rename = "type")]
     pub data_type: String,
     pub format: String,
     pub version: String,
-    #[serde(deserialize_with = "champion_list_deserializer")]
     #[serde(rename = "data")]
     pub champion_list: Vec<Champion>,
+    #[serde(skip)]
+    key_index: HashMap<i64, usize>,
+    #[serde(skip)]
+    name_index: HashMap<String, usize>,
+}
+
+#[derive(Deserialize)]
+struct RawChampionData {
+    #[serde(rename = "type")]
+    data_type: String,
+    format: String,
+    version: String,
+    #[serde(deserialize_with = "champion_list_deserializer")]
+    #[serde(rename = "data")]
+    champion_list: Vec<Champion>,
 }
 
-fn champion_list_deserializer<'de, D>(deserializer: D) -> Result<Vec<Champion>, D::Error>
+impl From<RawChampionData> for ChampionData {
+    fn from(raw: RawChampionData) -> Self {
+        let mut key_index = HashMap::with_capacity(raw.champion_list.len());
+        let mut name_index = HashMap::with_capacity(raw.champion_list.len() * 2);
+
+        for (i, champion) in raw.champion_list.iter().enumerate() {
+            key_index.insert(champion.key, i);
+            name_index.insert(champion.id.to_lowercase(), i);
+            name_index.insert(champion.name.to_lowercase(), i);
+        }
+
+        ChampionData {
+            data_type: raw.data_type,
+            format: raw.format,
+            version: raw.version,
+            champion_list: raw.champion_list,
+            key_index,
+            name_index,
+        }
+    }
+}
+
+impl ChampionData {
+    pub fn champion(&self, key: i64) -> Result<&Champion> {
+        self.key_index
+            .get(&key)
+            .map(|&i| &self.champion_list[i])
+            .ok_or(MyError::UnknownChampion(key))
+    }
+
+    /// Looks up a champion by its id (e.g. `MonkeyKing`) or display name (e.g. `Wukong`),
+    /// ignoring case.
+    pub fn champion_by_name(&self, name: &str) -> Option<&Champion> {
+        self.name_index
+            .get(&name.to_lowercase())
+            .map(|&i| &self.champion_list[i])
+    }
+
+    /// Name of the champion, or a placeholder if it isn't known in this patch. Unknown champions
+    /// are reported by `Api::ensure_champions_known`.
+    pub fn champion_name(&self, key: i64) -> String {
+        match self.champion(key) {
+            Ok(champion) => champion.name.to_owned(),
+            Err(_) => format!("Unknown Champion ({})", key),
+        }
+    }
+}
+
+fn champion_list_deserializer<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Vec<Champion>, D::Error>
 where
     D: serde::de::Deserializer<'de>,
 {
     let data: HashMap<String, Champion> = HashMap::deserialize(deserializer)?;
 
-    Ok(data.into_iter().map(|(_, v)| v).collect())
+    Ok(data.into_values().collect())
 }
 
-fn string_to_i64<'de, D>(deserializer: D) -> Result<i64, D::Error>
+pub fn string_to_i64<'de, D>(deserializer: D) -> std::result::Result<i64, D::Error>
 where
     D: serde::de::Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    Ok(s.parse()
-        .expect("Failed to deserialize champion key to i64."))
+    s.parse().map_err(serde::de::Error::custom)
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Champion {
     pub version: String,
@@ -48,7 +115,7 @@ pub struct Champion {
     pub stats: Stats,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Info {
     pub attack: i64,
@@ -57,7 +124,7 @@ pub struct Info {
     pub difficulty: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Image {
     pub full: String,
@@ -69,7 +136,7 @@ pub struct Image {
     pub h: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Stats {
     pub hp: f64,
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ddragon_champions::Image;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ItemData {
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub version: String,
+    #[serde(deserialize_with = "item_list_deserializer")]
+    #[serde(rename = "data")]
+    pub item_list: Vec<Item>,
+}
+
+fn item_list_deserializer<'de, D>(deserializer: D) -> Result<Vec<Item>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let data: HashMap<String, Item> = HashMap::deserialize(deserializer)?;
+
+    data.into_iter()
+        .map(|(id, mut item)| {
+            item.id = id.parse().map_err(serde::de::Error::custom)?;
+            Ok(item)
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    /// Taken from the key of the `data` map as items don't carry their own id.
+    #[serde(default)]
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub plaintext: String,
+    pub gold: Gold,
+    pub image: Image,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gold {
+    pub base: i64,
+    pub purchasable: bool,
+    pub total: i64,
+    pub sell: i64,
+}
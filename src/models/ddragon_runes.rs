@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RuneData {
+    pub trees: Vec<RuneTree>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuneTree {
+    pub id: i64,
+    pub key: String,
+    pub icon: String,
+    pub name: String,
+    pub slots: Vec<RuneSlot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuneSlot {
+    pub runes: Vec<Rune>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rune {
+    pub id: i64,
+    pub key: String,
+    pub icon: String,
+    pub name: String,
+    pub short_desc: String,
+    pub long_desc: String,
+}
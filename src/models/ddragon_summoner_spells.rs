@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ddragon_champions::{string_to_i64, Image};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SummonerSpellData {
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub version: String,
+    #[serde(deserialize_with = "summoner_spell_list_deserializer")]
+    #[serde(rename = "data")]
+    pub summoner_spell_list: Vec<SummonerSpell>,
+}
+
+fn summoner_spell_list_deserializer<'de, D>(deserializer: D) -> Result<Vec<SummonerSpell>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let data: HashMap<String, SummonerSpell> = HashMap::deserialize(deserializer)?;
+
+    Ok(data.into_values().collect())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SummonerSpell {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(deserialize_with = "string_to_i64")]
+    pub key: i64,
+    pub summoner_level: i64,
+    pub cooldown: Vec<f64>,
+    pub modes: Vec<String>,
+    pub image: Image,
+}
@@ -1,13 +1,58 @@
 use thiserror::Error;
 
+/// Number of characters of a response body kept in `MyError::Deserialize`.
+const BODY_SNIPPET_LENGTH: usize = 200;
+
 #[derive(Error, Debug)]
 pub enum MyError {
     #[error("This player is not currently in a game.")]
     NotInGame,
+    #[error("Champion {0} is not known in the loaded static data.")]
+    UnknownChampion(i64),
+    #[error("The API key was rejected, it is missing or invalid.")]
+    Unauthorized,
+    #[error("The API key is not allowed to access {endpoint}, it may have expired.")]
+    Forbidden { endpoint: String },
+    #[error("No data was found for endpoint: {endpoint}")]
+    NotFound { endpoint: String },
+    #[error("Rate limited by the {scope} limit, retry after {retry_after:?} seconds.")]
+    RateLimited {
+        retry_after: Option<u64>,
+        /// Limit that was exceeded, `application`, `method` or `service`.
+        scope: String,
+    },
+    #[error("The Riot API is unavailable for endpoint: {endpoint}")]
+    ServiceUnavailable { endpoint: String },
+    #[error("Request timed out for endpoint: {endpoint}")]
+    Timeout { endpoint: String },
+    #[error("Could not parse response of {endpoint}: {source} - {body_snippet}")]
+    Deserialize {
+        endpoint: String,
+        body_snippet: String,
+        source: serde_json::Error,
+    },
     #[error(transparent)]
     Reqwest(#[from] reqwest::Error),
     #[error(transparent)]
     Serde(#[from] serde_json::Error),
     #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl MyError {
+    pub fn deserialize(endpoint: String, body: &str, source: serde_json::Error) -> Self {
+        MyError::Deserialize {
+            endpoint,
+            body_snippet: body.chars().take(BODY_SNIPPET_LENGTH).collect(),
+            source,
+        }
+    }
+
+    /// Whether the error can't go away by itself, e.g. an expired API key, so retrying is
+    /// pointless.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, MyError::Unauthorized | MyError::Forbidden { .. })
+    }
+}
@@ -1,4 +1,5 @@
 use core::fmt;
+use std::collections::HashMap;
 use std::fmt::Formatter;
 
 use serde::{Deserialize, Serialize};
@@ -264,21 +265,21 @@ pub struct Tower {
 }
 
 #[derive(Debug)]
-pub struct LeagueMatchList<'a> {
-    pub match_info: LeagueMatchInfo<'a>,
+pub struct LeagueMatchList {
+    pub match_info: LeagueMatchInfo,
 }
 
-impl fmt::Display for LeagueMatchList<'_> {
+impl fmt::Display for LeagueMatchList {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{:#?}", self.match_info)
     }
 }
 
-impl<'a> LeagueMatchList<'a> {
-    pub fn new(match_ids: Vec<String>, api: &'a Api<'_>) -> Self {
+impl LeagueMatchList {
+    pub fn new(match_ids: Vec<String>, api: &Api) -> Self {
         let matches = match_ids
             .into_iter()
-            .map(|id| LeagueMatch::new(id, api))
+            .map(|id| LeagueMatch::new(id, api.clone()))
             .collect();
 
         Self {
@@ -288,30 +289,82 @@ impl<'a> LeagueMatchList<'a> {
 }
 
 #[derive(Debug)]
-pub struct LeagueMatchInfo<'a> {
-    pub matches: Vec<LeagueMatch<'a>>,
+pub struct LeagueMatchInfo {
+    pub matches: Vec<LeagueMatch>,
 }
 
 #[derive(Debug)]
-pub struct LeagueMatch<'a> {
+pub struct LeagueMatch {
     match_id: String,
-    api: &'a Api<'a>,
+    api: Api,
 }
 
-impl fmt::Display for LeagueMatch<'_> {
+impl fmt::Display for LeagueMatch {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{:#?}", self.match_id)
     }
 }
 
-impl<'a> LeagueMatch<'a> {
-    pub fn new(match_id: String, api: &'a Api<'a>) -> Self {
+impl LeagueMatch {
+    pub fn new(match_id: String, api: Api) -> Self {
         Self { match_id, api }
     }
 
+    pub fn match_id(&self) -> &str {
+        &self.match_id
+    }
+
     pub async fn match_data(&self) -> Result<MatchData> {
         self.api
             .get_match(lol_match::MatchEndpoint::ByMatchId(&self.match_id))
             .await
     }
 }
+
+/// Matches kept in a `MatchHistory`, well above the number of matches fetched per refresh so that
+/// evicted matches are never downloaded again.
+pub const MAX_HISTORY_MATCHES: usize = 200;
+
+/// Matches of a single summoner kept around for the whole tracking session, so that only new
+/// matches have to be downloaded when the history is refreshed.
+#[derive(Debug, Default)]
+pub struct MatchHistory {
+    matches: HashMap<String, MatchData>,
+}
+
+impl MatchHistory {
+    pub fn contains(&self, match_id: &str) -> bool {
+        self.matches.contains_key(match_id)
+    }
+
+    /// Adds `match_data`, dropping the oldest matches beyond `MAX_HISTORY_MATCHES`.
+    pub fn insert(&mut self, match_data: MatchData) {
+        self.matches
+            .insert(match_data.metadata.match_id.clone(), match_data);
+
+        while self.matches.len() > MAX_HISTORY_MATCHES {
+            let oldest = self
+                .matches
+                .values()
+                .min_by_key(|m| m.info.game_creation)
+                .map(|m| m.metadata.match_id.clone());
+
+            match oldest {
+                Some(match_id) => self.matches.remove(&match_id),
+                None => break,
+            };
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matches.is_empty()
+    }
+
+    pub fn matches(&self) -> impl Iterator<Item = &MatchData> {
+        self.matches.values()
+    }
+}
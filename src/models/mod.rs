@@ -1,7 +1,12 @@
 pub mod champion;
+pub mod champion_mastery;
 pub mod ddragon_champions;
+pub mod ddragon_items;
+pub mod ddragon_runes;
+pub mod ddragon_summoner_spells;
 pub mod error;
 pub mod leagues;
 pub mod lol_match;
 pub mod spectator;
+pub mod static_docs;
 pub mod summoner;
@@ -3,19 +3,21 @@ use std::fmt::Formatter;
 
 use anyhow::Context;
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 
-use crate::api::Api;
+use crate::api::{Api, Priority};
 use crate::endpoints::lol_match::{ByPuiidParams, ByPuiidParamsBuilder};
-use crate::endpoints::{leagues, lol_match, spectator, summoner};
+use crate::endpoints::{champion_mastery, leagues, lol_match, spectator, summoner};
 use crate::models::champion::ChampionWinRate;
+use crate::models::champion_mastery::ChampionMastery;
 use crate::models::leagues::LeagueRank;
-use crate::models::lol_match::LeagueMatchList;
-use crate::models::spectator::SpectatorInfo;
+use crate::models::lol_match::{LeagueMatch, LeagueMatchList, MatchData, MatchHistory};
+use crate::models::spectator::{BannedChampion, Participant, SpectatorInfo};
 use crate::Result;
 
 use super::error::MyError;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SummonerInfo {
     pub id: String,
@@ -28,70 +30,96 @@ pub struct SummonerInfo {
 }
 
 #[derive(Debug)]
-pub struct SummonerCurrentGameInfo<'a> {
-    pub summoner: Summoner<'a>,
+pub struct SummonerCurrentGameInfo {
+    pub summoner: Summoner,
     pub champion_id: i64,
     pub team_id: u8,
+    pub spells: (i64, i64),
+    pub keystone_id: Option<i64>,
+    pub sub_style_id: Option<i64>,
 }
 
-impl<'a> SummonerCurrentGameInfo<'a> {
-    pub fn new(summoner: Summoner<'a>, champion_id: i64, team_id: u8) -> Self {
+impl SummonerCurrentGameInfo {
+    pub fn new(summoner: Summoner, participant: &Participant) -> Self {
         SummonerCurrentGameInfo {
             summoner,
-            champion_id,
-            team_id,
+            champion_id: participant.champion_id,
+            team_id: participant.team_id as u8,
+            spells: (participant.spell1_id, participant.spell2_id),
+            keystone_id: participant.perks.perk_ids.first().copied(),
+            sub_style_id: participant.perks.perk_sub_style,
         }
     }
 }
 
 #[derive(Debug)]
-pub struct CurrentGameInfo<'a> {
+pub struct CurrentGameInfo {
     pub game_id: u64,
-    pub summoners: Vec<SummonerCurrentGameInfo<'a>>,
+    pub queue_id: i64,
+    pub map_id: i64,
+    pub game_mode: String,
+    /// Epoch milliseconds, 0 while the game is still loading.
+    pub game_start_time: i64,
+    pub bans: Vec<BannedChampion>,
+    pub summoners: Vec<SummonerCurrentGameInfo>,
 }
 
-impl<'a> CurrentGameInfo<'a> {
-    pub fn new(game_id: u64, summoners: Vec<SummonerCurrentGameInfo<'a>>) -> Self {
-        CurrentGameInfo { game_id, summoners }
+impl CurrentGameInfo {
+    pub fn new(spectator_info: SpectatorInfo, summoners: Vec<SummonerCurrentGameInfo>) -> Self {
+        CurrentGameInfo {
+            game_id: spectator_info.game_id as u64,
+            queue_id: spectator_info.game_queue_config_id,
+            map_id: spectator_info.map_id,
+            game_mode: spectator_info.game_mode,
+            game_start_time: spectator_info.game_start_time,
+            bans: spectator_info.banned_champions,
+            summoners,
+        }
     }
 }
 
-pub struct Summoner<'a> {
+#[derive(Clone)]
+pub struct Summoner {
     pub summoner_info: SummonerInfo,
-    api: &'a Api<'a>,
+    api: Api,
 }
 
-impl std::cmp::Eq for Summoner<'_> {}
+impl std::cmp::Eq for Summoner {}
 
-impl std::cmp::PartialEq for Summoner<'_> {
+impl std::cmp::PartialEq for Summoner {
     fn eq(&self, other: &Self) -> bool {
         self.summoner_info.id == other.summoner_info.id
     }
 }
 
-impl std::hash::Hash for Summoner<'_> {
+impl std::hash::Hash for Summoner {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.summoner_info.id.hash(state)
     }
 }
 
-impl fmt::Debug for Summoner<'_> {
+impl fmt::Debug for Summoner {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{:#?}", self.summoner_info)
     }
 }
 
-impl fmt::Display for Summoner<'_> {
+impl fmt::Display for Summoner {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{:#?}", self.summoner_info)
     }
 }
 
-impl<'a> Summoner<'a> {
-    pub fn new(summoner_info: SummonerInfo, api: &'a Api<'a>) -> Self {
+impl Summoner {
+    pub fn new(summoner_info: SummonerInfo, api: Api) -> Self {
         Self { summoner_info, api }
     }
 
+    /// The same summoner, with requests made on its behalf scheduled with `priority`.
+    pub fn with_priority(&self, priority: Priority) -> Self {
+        Self::new(self.summoner_info.clone(), self.api.with_priority(priority))
+    }
+
     pub async fn spectator(&self) -> Result<SpectatorInfo> {
         self.api
             .get_spectator(spectator::SpectatorEndpoint::BySummonerId(
@@ -108,16 +136,38 @@ impl<'a> Summoner<'a> {
             ))
             .await?;
 
-        let rank = league_ranks
-            .into_iter()
-            .find(|l| l.queue_type == "RANKED_SOLO_5x5")
-            .context("Could not find Solo Queue rank.")?;
+        solo_queue(league_ranks)
+    }
+
+    /// The solo queue rank as it is right now, e.g. to record LP gained in a game that just
+    /// ended.
+    pub async fn fresh_solo_queue_rank(&self) -> Result<LeagueRank> {
+        let league_ranks: Vec<LeagueRank> = self
+            .api
+            .get_leagues_fresh(leagues::LeagueRankEndpoint::BySummonerId(
+                &self.summoner_info.id,
+            ))
+            .await?;
+
+        solo_queue(league_ranks)
+    }
 
-        Ok(rank)
+    pub async fn champion_mastery(&self, champion_id: i64) -> Result<ChampionMastery> {
+        self.api
+            .get_champion_mastery(
+                champion_mastery::ChampionMasteryEndpoint::BySummonerIdAndChampion(
+                    &self.summoner_info.id,
+                    champion_id,
+                ),
+            )
+            .await
     }
 
-    pub async fn current_game_info(&self) -> Result<CurrentGameInfo<'_>> {
-        let current_game = self.spectator().await.map_err(|_| MyError::NotInGame)?;
+    pub async fn current_game_info(&self) -> Result<CurrentGameInfo> {
+        let current_game = self.spectator().await.map_err(|e| match e {
+            MyError::NotFound { .. } => MyError::NotInGame,
+            e => e,
+        })?;
 
         let mut cgs = Vec::with_capacity(10);
 
@@ -129,30 +179,30 @@ impl<'a> Summoner<'a> {
 
             match summoner {
                 Ok(summoner) => {
-                    let (champion_id, team_id) = current_game
+                    let participant = current_game
                         .participants
                         .iter()
                         .find(|p| p.summoner_id == summoner.summoner_info.id)
-                        .map(|p| (p.champion_id, p.team_id))
                         .expect("Couldn't map summoner to their champion");
 
-                    cgs.push(SummonerCurrentGameInfo::new(
-                        summoner,
-                        champion_id,
-                        team_id as u8,
-                    ));
+                    cgs.push(SummonerCurrentGameInfo::new(summoner, participant));
                 }
                 Err(e) => error!("{}", e),
             }
         }
 
-        Ok(CurrentGameInfo::new(current_game.game_id as u64, cgs))
+        Ok(CurrentGameInfo::new(current_game, cgs))
     }
 
-    pub async fn match_ids_list(
-        &self,
-        params: Option<ByPuiidParams>,
-    ) -> Result<LeagueMatchList<'_>> {
+    /// Match of a game this summoner played, from the game id seen while the game was live.
+    pub fn game_match(&self, game_id: u64) -> LeagueMatch {
+        LeagueMatch::new(
+            format!("{}_{}", self.api.region(), game_id),
+            self.api.clone(),
+        )
+    }
+
+    pub async fn match_ids_list(&self, params: Option<ByPuiidParams>) -> Result<LeagueMatchList> {
         let match_list = self
             .api
             .get_match::<Vec<String>>(lol_match::MatchEndpoint::ByPuuid(
@@ -161,53 +211,83 @@ impl<'a> Summoner<'a> {
             ))
             .await?;
 
-        Ok(LeagueMatchList::new(match_list, self.api))
+        Ok(LeagueMatchList::new(match_list, &self.api))
     }
 
-    pub async fn champion_win_rate(&self, champion_id: i64) -> Result<ChampionWinRate> {
-        let champion_name = self
-            .api
-            .champion_data
-            .champion_list
-            .iter()
-            .find(|c| c.key == champion_id)
-            .map(|c| c.name.to_owned())
-            .expect("Couldn't find champion in system.");
+    pub async fn recent_matches(&self, count: u8) -> Result<Vec<MatchData>> {
+        let match_list = self
+            .match_ids_list(Some(
+                ByPuiidParamsBuilder::default()
+                    .count(count)
+                    .build()
+                    .expect("Failed to build Puiid Parameters."),
+            ))
+            .await?;
 
-        let mut wins = 0;
-        let mut losses = 0;
+        let mut matches = Vec::with_capacity(match_list.match_info.matches.len());
 
-        if let Ok(match_list) = self
+        for m in match_list.match_info.matches.iter() {
+            match m.match_data().await {
+                Ok(match_data) => matches.push(match_data),
+                Err(e) => error!("{}", e),
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Downloads the matches of the last `count` not in `history` yet. The lock is only held while
+    /// checking and inserting matches, so the history can be read while it is refreshed.
+    pub async fn refresh_match_history(
+        &self,
+        history: &RwLock<MatchHistory>,
+        count: u8,
+    ) -> Result<()> {
+        let match_list = self
             .match_ids_list(Some(
                 ByPuiidParamsBuilder::default()
-                    .count(15)
+                    .count(count)
                     .build()
                     .expect("Failed to build Puiid Parameters."),
             ))
-            .await
-        {
-            for m in match_list.match_info.matches.iter() {
-                let match_data = m.match_data().await;
-
-                match match_data {
-                    Ok(match_data) => {
-                        if let Some(match_result) = match_data
-                            .info
-                            .participants
-                            .iter()
-                            .find(|p| {
-                                p.puuid == self.summoner_info.puuid && p.champion_id == champion_id
-                            })
-                            .map(|p| p.win)
-                        {
-                            if match_result {
-                                wins += 1;
-                            } else {
-                                losses += 1;
-                            }
-                        }
-                    }
-                    Err(e) => error!("{}", e),
+            .await?;
+
+        for m in match_list.match_info.matches.iter() {
+            if history.read().await.contains(m.match_id()) {
+                continue;
+            }
+
+            match m.match_data().await {
+                Ok(match_data) => history.write().await.insert(match_data),
+                Err(e) => error!("{}", e),
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn champion_win_rate(
+        &self,
+        champion_id: i64,
+        matches: &[MatchData],
+    ) -> Result<ChampionWinRate> {
+        let champion_name = self.api.static_data().champion_name(champion_id);
+
+        let mut wins = 0;
+        let mut losses = 0;
+
+        for match_data in matches.iter() {
+            if let Some(match_result) = match_data
+                .info
+                .participants
+                .iter()
+                .find(|p| p.puuid == self.summoner_info.puuid && p.champion_id == champion_id)
+                .map(|p| p.win)
+            {
+                if match_result {
+                    wins += 1;
+                } else {
+                    losses += 1;
                 }
             }
         }
@@ -220,3 +300,12 @@ impl<'a> Summoner<'a> {
         ))
     }
 }
+
+fn solo_queue(league_ranks: Vec<LeagueRank>) -> Result<LeagueRank> {
+    let rank = league_ranks
+        .into_iter()
+        .find(|l| l.queue_type == "RANKED_SOLO_5x5")
+        .context("Could not find Solo Queue rank.")?;
+
+    Ok(rank)
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::encounters;
+use crate::analysis::premade::{self, PremadePlayer};
+use crate::analysis::roles::RoleInfo;
+use crate::analysis::smurf::{SmurfConfig, SmurfScore};
+use crate::api::Api;
+use crate::columns::{self, SortKey};
+use crate::models::champion::{ChampionWinRate, RecentMatch};
+use crate::models::lol_match::MatchHistory;
+use crate::models::summoner::{CurrentGameInfo, Summoner};
+use crate::util::{now_millis, StringExt};
+
+/// Number of recent matches loaded for every player in the game.
+const RECENT_MATCH_COUNT: u8 = 15;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BanInfo {
+    pub team_id: i64,
+    pub champion_id: i64,
+    pub champion_name: String,
+    pub pick_turn: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameReport {
+    pub game_id: u64,
+    pub queue_id: i64,
+    pub queue_name: Option<String>,
+    pub map_id: i64,
+    pub map_name: Option<String>,
+    pub game_mode: String,
+    pub game_start_time: i64,
+    pub bans: Vec<BanInfo>,
+    pub players: Vec<ChampionWinRate>,
+}
+
+impl GameReport {
+    pub fn bans_string(&self, team_id: i64) -> String {
+        let bans = self
+            .bans
+            .iter()
+            .filter(|b| b.team_id == team_id)
+            .map(|b| b.champion_name.as_str())
+            .collect::<Vec<_>>();
+
+        if bans.is_empty() {
+            "None".to_owned()
+        } else {
+            bans.join(", ")
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let queue = self
+            .queue_name
+            .clone()
+            .unwrap_or_else(|| format!("Queue {}", self.queue_id));
+
+        let map = self
+            .map_name
+            .clone()
+            .unwrap_or_else(|| format!("Map {}", self.map_id));
+
+        let mut summary = format!("{} - {} - {}", self.game_mode, queue, map);
+
+        if self.game_start_time > 0 {
+            let elapsed = (now_millis() - self.game_start_time).max(0) / 1000;
+
+            summary.push_str(&format!(
+                " - In game for {}:{:02}",
+                elapsed / 60,
+                elapsed % 60
+            ));
+        }
+
+        summary
+    }
+}
+
+pub async fn game_report(
+    api: &Api,
+    my_summoner: &Summoner,
+    cgi: &CurrentGameInfo,
+    history: &MatchHistory,
+    smurf_config: &SmurfConfig,
+    sort_keys: &[SortKey],
+) -> GameReport {
+    let mut results = Vec::with_capacity(10);
+    let mut recent_matches = HashMap::with_capacity(10);
+
+    api.ensure_champions_known(
+        cgi.summoners
+            .iter()
+            .map(|s| s.champion_id)
+            .chain(cgi.bans.iter().map(|b| b.champion_id).filter(|id| *id > 0)),
+    )
+    .await;
+
+    let static_data = api.static_data();
+
+    for summoner_current_game_info in cgi.summoners.iter() {
+        let summoner = &summoner_current_game_info.summoner;
+
+        let matches = match summoner.recent_matches(RECENT_MATCH_COUNT).await {
+            Ok(matches) => matches,
+            Err(e) => {
+                info!(
+                    "Couldn't find recent matches for summoner: {} - {}",
+                    &summoner.summoner_info.name, e
+                );
+                continue;
+            }
+        };
+
+        if let Ok(mut cwr) =
+            summoner.champion_win_rate(summoner_current_game_info.champion_id, &matches)
+        {
+            cwr.team_id = summoner_current_game_info.team_id;
+            cwr.summoner_name = summoner.summoner_info.name.clone();
+            cwr.puuid = summoner.summoner_info.puuid.clone();
+            cwr.role = RoleInfo::new(
+                &cwr.puuid,
+                static_data
+                    .champion(summoner_current_game_info.champion_id)
+                    .ok(),
+                summoner_current_game_info.spells,
+                &matches,
+            );
+
+            let (spell1, spell2) = summoner_current_game_info.spells;
+
+            cwr.spells = [spell1, spell2]
+                .iter()
+                .map(|id| {
+                    static_data
+                        .summoner_spell(*id)
+                        .map(|s| s.name.to_owned())
+                        .unwrap_or_else(|| format!("Spell {}", id))
+                })
+                .collect();
+
+            cwr.keystone = summoner_current_game_info
+                .keystone_id
+                .and_then(|id| static_data.rune(id))
+                .map(|r| r.name.to_owned());
+
+            cwr.secondary_tree = summoner_current_game_info
+                .sub_style_id
+                .and_then(|id| static_data.rune_tree(id))
+                .map(|t| t.name.to_owned());
+
+            cwr.mastery = summoner
+                .champion_mastery(summoner_current_game_info.champion_id)
+                .await
+                .ok();
+
+            cwr.recent_matches = matches
+                .iter()
+                .filter_map(|m| {
+                    let p = m.info.participants.iter().find(|p| p.puuid == cwr.puuid)?;
+
+                    Some(RecentMatch {
+                        match_id: m.metadata.match_id.clone(),
+                        game_creation: m.info.game_creation,
+                        queue_id: m.info.queue_id,
+                        champion_id: p.champion_id,
+                        champion_name: static_data.champion_name(p.champion_id),
+                        kills: p.kills,
+                        deaths: p.deaths,
+                        assists: p.assists,
+                        won: p.win,
+                    })
+                })
+                .collect();
+
+            if summoner != my_summoner {
+                cwr.encounters = encounters::find_encounters(
+                    &my_summoner.summoner_info.puuid,
+                    &cwr.puuid,
+                    history.matches(),
+                );
+
+                for encounter in cwr.encounters.iter_mut() {
+                    let match_static_data = api
+                        .static_data_for_game_version(&encounter.game_version)
+                        .await;
+
+                    encounter.resolve_names(&match_static_data);
+                }
+            }
+
+            let rank = summoner.solo_queue_rank().await.ok();
+
+            if let Some(rank) = &rank {
+                cwr.rank = format!("{} {}", rank.tier.to_owned().title_case(), rank.rank);
+            };
+
+            cwr.summoner_level = summoner.summoner_info.summoner_level;
+            cwr.smurf = SmurfScore::new(
+                smurf_config,
+                cwr.summoner_level,
+                rank.as_ref(),
+                &cwr.puuid,
+                &matches,
+            );
+            cwr.solo_queue_rank = rank;
+
+            results.push(cwr);
+        } else {
+            info!(
+                "Couldn't find win rate for summoner: {}",
+                &summoner.summoner_info.name
+            );
+        }
+
+        recent_matches.insert(summoner.summoner_info.puuid.clone(), matches);
+    }
+
+    let players = cgi
+        .summoners
+        .iter()
+        .map(|s| PremadePlayer {
+            puuid: &s.summoner.summoner_info.puuid,
+            team_id: s.team_id,
+        })
+        .collect::<Vec<_>>();
+
+    let premades = premade::detect_premades(&players, &recent_matches);
+
+    for cwr in results.iter_mut() {
+        cwr.premade_group = premades.get(&cwr.puuid).copied();
+    }
+
+    columns::sort_players(&mut results, sort_keys);
+
+    let bans = cgi
+        .bans
+        .iter()
+        .filter(|b| b.champion_id > 0)
+        .map(|b| BanInfo {
+            team_id: b.team_id,
+            champion_id: b.champion_id,
+            champion_name: static_data.champion_name(b.champion_id),
+            pick_turn: b.pick_turn,
+        })
+        .collect();
+
+    GameReport {
+        game_id: cgi.game_id,
+        queue_id: cgi.queue_id,
+        queue_name: static_data
+            .queue(cgi.queue_id)
+            .and_then(|q| q.description.clone()),
+        map_id: cgi.map_id,
+        map_name: static_data.map(cgi.map_id).map(|m| m.map_name.clone()),
+        game_mode: cgi.game_mode.clone(),
+        game_start_time: cgi.game_start_time,
+        bans,
+        players: results,
+    }
+}
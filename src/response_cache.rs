@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+use crate::endpoints::CacheTtl;
+use crate::metrics;
+use crate::session::write_atomic;
+use crate::util::now_millis;
+use crate::Result;
+
+/// Entries kept before the oldest ones are evicted, to bound memory use in long sessions.
+const MAX_ENTRIES: usize = 5000;
+
+/// Total size of the response bodies saved between sessions. Match bodies are large and cached
+/// forever, so only the most recent responses are kept.
+pub const MAX_PERSISTED_BYTES: usize = 20 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Requests that waited for an identical request already in flight instead of being sent.
+    pub coalesced: u64,
+}
+
+impl CacheStats {
+    /// Share (0-1) of responses not requested from the API.
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses + self.coalesced;
+
+        if total == 0 {
+            return 0.0;
+        }
+
+        (self.hits + self.coalesced) as f64 / total as f64
+    }
+}
+
+/// Entry as saved on disk, with the expiry as epoch milliseconds.
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    key: String,
+    body: Arc<str>,
+    expires_at: Option<i64>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: Arc<str>,
+    inserted_at: Instant,
+    expires_at: Option<Instant>,
+}
+
+impl Entry {
+    fn is_expired(&self) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= Instant::now())
+    }
+}
+
+/// In-memory cache of API response bodies keyed by endpoint URL. Identical requests made while
+/// one is already in flight wait for its response instead of being sent again.
+#[derive(Debug, Default)]
+pub struct ResponseCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    /// Senders of requests in flight, `None` is sent if the request fails.
+    in_flight: Mutex<HashMap<String, broadcast::Sender<Option<Arc<str>>>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+/// Stops coalescing onto a request once it finishes or is cancelled.
+struct InFlightGuard<'a> {
+    cache: &'a ResponseCache,
+    key: &'a str,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.cache
+            .in_flight
+            .lock()
+            .expect("In flight requests lock poisoned")
+            .remove(self.key);
+    }
+}
+
+impl ResponseCache {
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the cached response for `key`, or the response of `fetch` which is cached for
+    /// `ttl`.
+    pub async fn get_or_fetch<F, Fut>(&self, key: &str, ttl: CacheTtl, fetch: F) -> Result<Arc<str>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        if let Some(body) = self.cached(key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            metrics::global().record_cache_hit();
+            return Ok(body);
+        }
+
+        let sender = {
+            let mut in_flight = self
+                .in_flight
+                .lock()
+                .expect("In flight requests lock poisoned");
+
+            match in_flight.get(key) {
+                Some(sender) => Err(sender.subscribe()),
+                None => {
+                    let (sender, _) = broadcast::channel(1);
+                    in_flight.insert(key.to_owned(), sender.clone());
+                    Ok(sender)
+                }
+            }
+        };
+
+        let sender = match sender {
+            Ok(sender) => sender,
+            Err(mut receiver) => {
+                // Fall back to making the request if the one in flight fails or is cancelled.
+                if let Ok(Some(body)) = receiver.recv().await {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                    metrics::global().record_cache_coalesced();
+                    return Ok(body);
+                }
+
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                metrics::global().record_cache_miss();
+                return Ok(Arc::from(fetch().await?));
+            }
+        };
+
+        let guard = InFlightGuard { cache: self, key };
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        metrics::global().record_cache_miss();
+
+        let body = match fetch().await {
+            Ok(body) => Arc::<str>::from(body),
+            Err(e) => {
+                drop(guard);
+                let _ = sender.send(None);
+                return Err(e);
+            }
+        };
+
+        self.insert(key, ttl, Arc::clone(&body));
+
+        drop(guard);
+        let _ = sender.send(Some(Arc::clone(&body)));
+
+        Ok(body)
+    }
+
+    /// Always makes the request with `fetch`, replacing the cached response for `key` on success.
+    pub async fn refresh<F, Fut>(&self, key: &str, ttl: CacheTtl, fetch: F) -> Result<Arc<str>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        metrics::global().record_cache_miss();
+
+        let body = Arc::<str>::from(fetch().await?);
+
+        self.insert(key, ttl, Arc::clone(&body));
+
+        Ok(body)
+    }
+
+    fn cached(&self, key: &str) -> Option<Arc<str>> {
+        self.entries
+            .lock()
+            .expect("Response cache lock poisoned")
+            .get(key)
+            .filter(|e| !e.is_expired())
+            .map(|e| Arc::clone(&e.body))
+    }
+
+    fn insert(&self, key: &str, ttl: CacheTtl, body: Arc<str>) {
+        let now = Instant::now();
+
+        let expires_at = match ttl {
+            CacheTtl::Never => return,
+            CacheTtl::For(duration) => Some(now + duration),
+            CacheTtl::Forever => None,
+        };
+
+        self.insert_entry(key.to_owned(), body, expires_at);
+    }
+
+    fn insert_entry(&self, key: String, body: Arc<str>, expires_at: Option<Instant>) {
+        let mut entries = self.entries.lock().expect("Response cache lock poisoned");
+
+        if entries.len() >= MAX_ENTRIES {
+            entries.retain(|_, e| !e.is_expired());
+        }
+
+        if entries.len() >= MAX_ENTRIES {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, e)| e.inserted_at)
+                .map(|(k, _)| k.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            key,
+            Entry {
+                body,
+                inserted_at: Instant::now(),
+                expires_at,
+            },
+        );
+    }
+
+    /// Writes the most recent entries that haven't expired to `path`, up to `MAX_PERSISTED_BYTES`,
+    /// so that they can be loaded in the next session.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let now = Instant::now();
+        let now_millis = now_millis();
+
+        let entries = self.entries.lock().expect("Response cache lock poisoned");
+
+        let mut entries = entries
+            .iter()
+            .filter(|(_, e)| !e.is_expired())
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|(_, e)| std::cmp::Reverse(e.inserted_at));
+
+        let mut size = 0;
+
+        let stored = entries
+            .into_iter()
+            .take_while(|(_, e)| {
+                size += e.body.len();
+                size <= MAX_PERSISTED_BYTES
+            })
+            .map(|(key, e)| StoredEntry {
+                key: key.clone(),
+                body: Arc::clone(&e.body),
+                expires_at: e
+                    .expires_at
+                    .map(|t| now_millis + t.saturating_duration_since(now).as_millis() as i64),
+            })
+            .collect::<Vec<_>>();
+
+        write_atomic(path, serde_json::to_string(&stored)?)
+    }
+
+    /// Adds the entries saved at `path` that haven't expired since, if there are any.
+    pub fn load(&self, path: &Path) -> Result<()> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let now = Instant::now();
+        let now_millis = now_millis();
+
+        for entry in serde_json::from_str::<Vec<StoredEntry>>(&contents)? {
+            let expires_at = match entry.expires_at {
+                Some(expires_at) if expires_at <= now_millis => continue,
+                Some(expires_at) => {
+                    Some(now + Duration::from_millis((expires_at - now_millis) as u64))
+                }
+                None => None,
+            };
+
+            self.insert_entry(entry.key, entry.body, expires_at);
+        }
+
+        Ok(())
+    }
+}
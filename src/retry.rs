@@ -0,0 +1,167 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, Response, StatusCode};
+
+use crate::metrics;
+use crate::Result;
+
+/// When and how often failed requests are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry, multiplied by `backoff_multiplier` for every further retry.
+    pub initial_backoff: Duration,
+    pub backoff_multiplier: f64,
+    pub max_backoff: Duration,
+    /// Fraction (0-1) of each delay that is randomised, so that concurrent requests don't all
+    /// retry at the same moment.
+    pub jitter: f64,
+    pub retryable_statuses: Vec<StatusCode>,
+    /// Whether connection errors and timeouts are retried.
+    pub retry_network_errors: bool,
+    /// Time after the first attempt from which no more attempts are started.
+    pub deadline: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            backoff_multiplier: 2.0,
+            max_backoff: Duration::from_secs(10),
+            jitter: 0.25,
+            retryable_statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            retry_network_errors: true,
+            deadline: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying after `attempt` (starting at 1) failed, without jitter.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self
+            .backoff_multiplier
+            .powi(attempt.saturating_sub(1) as i32);
+
+        Duration::from_secs_f64(
+            (self.initial_backoff.as_secs_f64() * factor).min(self.max_backoff.as_secs_f64()),
+        )
+    }
+
+    pub fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    pub fn is_retryable_error(&self, error: &reqwest::Error) -> bool {
+        self.retry_network_errors
+            && (error.is_timeout() || error.is_connect() || error.is_request())
+    }
+
+    pub fn start(&self) -> RetryState<'_> {
+        RetryState {
+            policy: self,
+            attempts: 0,
+            started: Instant::now(),
+        }
+    }
+
+    fn with_jitter(&self, delay: Duration) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+
+        delay.mul_f64(1.0 + jitter * (2.0 * random_fraction() - 1.0))
+    }
+}
+
+/// Attempts made so far for a single request.
+#[derive(Debug)]
+pub struct RetryState<'a> {
+    policy: &'a RetryPolicy,
+    attempts: u32,
+    started: Instant,
+}
+
+impl RetryState<'_> {
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Records a failed attempt and returns how long to wait before the next one, or `None` if
+    /// the policy gives up. A `retry_after` asked for by the server replaces the backoff.
+    pub fn next_delay(&mut self, retry_after: Option<Duration>) -> Option<Duration> {
+        self.attempts += 1;
+
+        if self.attempts >= self.policy.max_attempts {
+            return None;
+        }
+
+        let delay = match retry_after {
+            Some(retry_after) => retry_after,
+            None => self.policy.with_jitter(self.policy.backoff(self.attempts)),
+        };
+
+        match self.policy.deadline {
+            Some(deadline) if self.started.elapsed() + delay > deadline => None,
+            _ => {
+                metrics::global().record_retry();
+
+                Some(delay)
+            }
+        }
+    }
+}
+
+/// GETs `url`, retrying as allowed by `policy`. Error statuses that are not retried, or are still
+/// returned once the policy gives up, are turned into errors.
+pub async fn get(client: &Client, policy: &RetryPolicy, url: &str) -> Result<Response> {
+    let mut retry = policy.start();
+
+    loop {
+        let (error, retry_after) = match client.get(url).send().await {
+            Ok(res) => {
+                let status = res.status();
+                let retry_after = retry_after(&res);
+
+                match res.error_for_status() {
+                    Ok(res) => return Ok(res),
+                    Err(e) if policy.is_retryable_status(status) => (e, retry_after),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            Err(e) if policy.is_retryable_error(&e) => (e, None),
+            Err(e) => return Err(e.into()),
+        };
+
+        match retry.next_delay(retry_after) {
+            Some(delay) => tokio::time::sleep(delay).await,
+            None => return Err(error.into()),
+        }
+    }
+}
+
+/// Delay asked for by the `Retry-After` header, ignored if it isn't a number of seconds.
+pub fn retry_after(res: &Response) -> Option<Duration> {
+    res.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Random number in `[0, 1)`. `RandomState` is randomly seeded, which is plenty for spreading out
+/// retries without pulling in an RNG.
+fn random_fraction() -> f64 {
+    let hash = RandomState::new().build_hasher().finish();
+
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::api::ApiRegion;
+use crate::events::LpSnapshot;
+use crate::report::GameReport;
+use crate::util::now_millis;
+use crate::Result;
+
+/// LP snapshots kept in the session state, enough for several months of games.
+const MAX_LP_SNAPSHOTS: usize = 1000;
+
+/// Time after which notified games are forgotten, long after any game could still be running.
+const NOTIFIED_GAME_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Tracker state kept between runs for one summoner.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Epoch milliseconds at which reports were shown, by game id.
+    pub notified_games: HashMap<u64, i64>,
+    pub lp_history: Vec<LpSnapshot>,
+    /// Report of the last game notified, shown again if tracking restarts while it is running.
+    pub last_report: Option<Arc<GameReport>>,
+}
+
+impl SessionState {
+    /// State of a summoner is stored in `<data dir>/league_notify/sessions/<region>-<name>.json`.
+    pub fn path(region: &ApiRegion, summoner_name: &str) -> Result<PathBuf> {
+        Ok(data_dir()?.join("sessions").join(format!(
+            "{}-{}.json",
+            region,
+            file_name(summoner_name)
+        )))
+    }
+
+    /// Loads the state stored at `path`, or an empty state if there is none yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut state: SessionState = match fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => SessionState::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        state.prune();
+
+        Ok(state)
+    }
+
+    pub fn is_notified(&self, game_id: u64) -> bool {
+        self.notified_games.contains_key(&game_id)
+    }
+
+    /// Records that the report of a game has been shown.
+    pub fn mark_notified(&mut self, report: Arc<GameReport>) {
+        self.prune();
+
+        self.notified_games.insert(report.game_id, now_millis());
+        self.last_report = Some(report);
+    }
+
+    /// The saved report of `game_id`, if it was the last game notified.
+    pub fn report(&self, game_id: u64) -> Option<&Arc<GameReport>> {
+        self.last_report.as_ref().filter(|r| r.game_id == game_id)
+    }
+
+    /// Adds `snapshot` to the LP history, dropping the oldest snapshots beyond
+    /// `MAX_LP_SNAPSHOTS`.
+    pub fn push_lp_snapshot(&mut self, snapshot: LpSnapshot) {
+        self.lp_history.push(snapshot);
+
+        let excess = self.lp_history.len().saturating_sub(MAX_LP_SNAPSHOTS);
+        self.lp_history.drain(..excess);
+    }
+
+    /// Forgets games notified more than `NOTIFIED_GAME_MAX_AGE` ago.
+    fn prune(&mut self) {
+        let cutoff = now_millis() - NOTIFIED_GAME_MAX_AGE.as_millis() as i64;
+
+        self.notified_games
+            .retain(|_, notified_at| *notified_at > cutoff);
+
+        if let Some(report) = &self.last_report {
+            if !self.notified_games.contains_key(&report.game_id) {
+                self.last_report = None;
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        write_atomic(path, serde_json::to_string(self)?)
+    }
+}
+
+/// Where the response cache of `region` is stored between runs.
+pub fn response_cache_path(region: &ApiRegion) -> Result<PathBuf> {
+    Ok(dirs::cache_dir()
+        .context("Could not find a cache directory.")?
+        .join("league_notify")
+        .join(format!("responses-{}.json", region)))
+}
+
+/// Where log lines are written while the terminal UI runs.
+pub fn log_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("league_notify.log"))
+}
+
+fn data_dir() -> Result<PathBuf> {
+    Ok(dirs::data_dir()
+        .context("Could not find a data directory.")?
+        .join("league_notify"))
+}
+
+/// Summoner names can contain characters that aren't allowed in file names on every platform.
+fn file_name(summoner_name: &str) -> String {
+    summoner_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Writes to a temporary file first, so that a run stopped halfway never leaves a corrupt file.
+pub(crate) fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let tmp_path = path.with_extension("tmp");
+
+    fs::write(&tmp_path, contents)?;
+    fs::rename(tmp_path, path)?;
+
+    Ok(())
+}
+
+/// What happened during a tracking session, shown on exit.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub duration: Duration,
+    pub games_tracked: usize,
+    /// LP gained (or lost) in solo queue, across divisions.
+    pub lp_change: Option<i64>,
+    pub requests_sent: u64,
+}
+
+impl SessionSummary {
+    /// LP change between the first and last of `snapshots`.
+    pub fn lp_change(snapshots: &[LpSnapshot]) -> Option<i64> {
+        let first = snapshots.first()?.ladder_points()?;
+        let last = snapshots.last()?.ladder_points()?;
+
+        Some(last - first)
+    }
+}
+
+impl fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = self.duration.as_secs() / 60;
+
+        write!(
+            f,
+            "Tracked {} game(s) in {}h {:02}m",
+            self.games_tracked,
+            minutes / 60,
+            minutes % 60
+        )?;
+
+        if let Some(lp_change) = self.lp_change {
+            write!(f, " - LP {:+}", lp_change)?;
+        }
+
+        write!(f, " - {} API requests", self.requests_sent)
+    }
+}
@@ -1,52 +1,124 @@
 use comfy_table::Table;
 
-use crate::models::champion::ChampionWinRate;
-use crate::models::summoner::Summoner;
-
-pub fn generate_table(my_summoner: &Summoner<'_>, results: Vec<ChampionWinRate>) -> Table {
-    let player_color = comfy_table::Color::Rgb {
-        r: 239,
-        g: 159,
-        b: 8,
-    };
-
-    let team_1_colour = comfy_table::Color::Rgb {
-        r: 4,
-        g: 151,
-        b: 211,
-    };
-
-    let team_2_colour = comfy_table::Color::Rgb {
-        r: 216,
-        g: 58,
-        b: 62,
-    };
-
-    let no_colour = comfy_table::Color::Reset;
+use league_notify::columns::Column;
+use league_notify::models::champion::ChampionWinRate;
+use league_notify::models::summoner::Summoner;
+use league_notify::report::GameReport;
 
+pub const PLAYER_COLOUR: comfy_table::Color = comfy_table::Color::Rgb {
+    r: 239,
+    g: 159,
+    b: 8,
+};
+
+pub const TEAM_1_COLOUR: comfy_table::Color = comfy_table::Color::Rgb {
+    r: 4,
+    g: 151,
+    b: 211,
+};
+
+pub const TEAM_2_COLOUR: comfy_table::Color = comfy_table::Color::Rgb {
+    r: 216,
+    g: 58,
+    b: 62,
+};
+
+pub const PREMADE_COLOURS: [comfy_table::Color; 4] = [
+    comfy_table::Color::Rgb {
+        r: 46,
+        g: 204,
+        b: 113,
+    },
+    comfy_table::Color::Rgb {
+        r: 155,
+        g: 89,
+        b: 182,
+    },
+    comfy_table::Color::Rgb {
+        r: 241,
+        g: 196,
+        b: 15,
+    },
+    comfy_table::Color::Rgb {
+        r: 26,
+        g: 188,
+        b: 156,
+    },
+];
+
+pub fn team_colour(team_id: i64) -> comfy_table::Color {
+    match team_id {
+        100 => TEAM_1_COLOUR,
+        200 => TEAM_2_COLOUR,
+        _ => comfy_table::Color::Reset,
+    }
+}
+
+pub fn generate_table(
+    my_summoner: &Summoner,
+    report: &GameReport,
+    columns: &[Column],
+    verbose: bool,
+) -> Table {
+    let mut table = Table::new();
+
+    table.set_header(
+        columns
+            .iter()
+            .map(|c| comfy_table::Cell::new(c.header()).add_attribute(comfy_table::Attribute::Bold))
+            .collect::<Vec<_>>(),
+    );
+
+    for cwr in report.players.iter() {
+        table.add_row(
+            columns
+                .iter()
+                .map(|c| {
+                    let cell = comfy_table::Cell::new(c.value(cwr, verbose));
+
+                    match cell_colour(*c, cwr, &my_summoner.summoner_info.name) {
+                        comfy_table::Color::Reset => cell,
+                        colour => cell.fg(colour),
+                    }
+                })
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    table
+}
+
+pub fn premade_colour(group: u8) -> comfy_table::Color {
+    PREMADE_COLOURS[(group as usize - 1) % PREMADE_COLOURS.len()]
+}
+
+/// Colour of `cwr`'s value in `column`, also used by the terminal UI. `Reset` keeps the default
+/// colour.
+pub fn cell_colour(column: Column, cwr: &ChampionWinRate, my_name: &str) -> comfy_table::Color {
+    match column {
+        Column::Champion | Column::Summoner if cwr.summoner_name == my_name => PLAYER_COLOUR,
+        Column::Champion | Column::Summoner => team_colour(cwr.team_id as i64),
+        Column::Role if cwr.role.off_role => comfy_table::Color::Yellow,
+        Column::Premade => cwr
+            .premade_group
+            .map_or(comfy_table::Color::Reset, premade_colour),
+        Column::Smurf if cwr.smurf.flagged => comfy_table::Color::Red,
+        _ => comfy_table::Color::Reset,
+    }
+}
+
+pub fn generate_bans_table(report: &GameReport) -> Table {
     let mut table = Table::new();
 
     table.set_header(vec![
-        comfy_table::Cell::new("Champion Name").add_attribute(comfy_table::Attribute::Bold),
-        comfy_table::Cell::new("Win Rate").add_attribute(comfy_table::Attribute::Bold),
-        comfy_table::Cell::new("Rank (Solo Queue)").add_attribute(comfy_table::Attribute::Bold),
+        comfy_table::Cell::new("Team").add_attribute(comfy_table::Attribute::Bold),
+        comfy_table::Cell::new("Bans").add_attribute(comfy_table::Attribute::Bold),
     ]);
 
-    for cwr in results.iter() {
-        let name_colour = if cwr.summoner_name == my_summoner.summoner_info.name {
-            player_color
-        } else {
-            match cwr.team_id {
-                100 => team_1_colour,
-                200 => team_2_colour,
-                _ => no_colour,
-            }
-        };
-
+    for (team_id, team_name) in [(100, "Blue"), (200, "Red")] {
         table.add_row(vec![
-            comfy_table::Cell::new(cwr.champion_name.clone()).fg(name_colour),
-            comfy_table::Cell::new(&cwr.win_rate_string()),
-            comfy_table::Cell::new(cwr.rank.clone()),
+            comfy_table::Cell::new(team_name).fg(team_colour(team_id)),
+            comfy_table::Cell::new(report.bans_string(team_id)),
         ]);
     }
 
@@ -0,0 +1,173 @@
+use std::time::Instant;
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::dashboard::DashboardState;
+use league_notify::api::RateLimitBudget;
+use league_notify::columns::Column;
+use league_notify::events::TrackerEvent;
+use league_notify::report::GameReport;
+use league_notify::response_cache::CacheStats;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Tab {
+    Game,
+    History,
+}
+
+impl Tab {
+    pub const TITLES: [&'static str; 2] = ["Live Game", "History"];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Tab::Game => 0,
+            Tab::History => 1,
+        }
+    }
+
+    fn toggle(&self) -> Self {
+        match self {
+            Tab::Game => Tab::History,
+            Tab::History => Tab::Game,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PollStatus {
+    /// Loading static data and looking up the summoner.
+    Starting,
+    Waiting,
+    LoadingGame,
+    InGame,
+    Stopped(String),
+}
+
+impl PollStatus {
+    pub fn description(&self) -> String {
+        match self {
+            PollStatus::Starting => "Starting...".to_owned(),
+            PollStatus::Waiting => "Waiting for a game...".to_owned(),
+            PollStatus::LoadingGame => "Game detected, loading info...".to_owned(),
+            PollStatus::InGame => "In game".to_owned(),
+            PollStatus::Stopped(reason) => format!("Tracking stopped: {}", reason),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct App {
+    pub summoner_name: String,
+    pub columns: Vec<Column>,
+    pub verbose: bool,
+    pub state: DashboardState,
+    pub status: PollStatus,
+    pub last_poll: Option<Instant>,
+    pub rate_limits: Vec<RateLimitBudget>,
+    pub cache: CacheStats,
+    pub tab: Tab,
+    /// Index into the current game's players.
+    pub selected_player: usize,
+    /// Whether the selected player's details are shown.
+    pub expanded: bool,
+    pub selected_history: usize,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(summoner_name: &str, columns: &[Column], verbose: bool) -> Self {
+        App {
+            summoner_name: summoner_name.to_owned(),
+            columns: columns.to_vec(),
+            verbose,
+            state: DashboardState::default(),
+            status: PollStatus::Starting,
+            last_poll: None,
+            rate_limits: Vec::new(),
+            cache: CacheStats::default(),
+            tab: Tab::Game,
+            selected_player: 0,
+            expanded: false,
+            selected_history: 0,
+            quit: false,
+        }
+    }
+
+    pub fn current_game(&self) -> Option<&GameReport> {
+        self.state.current_game.as_deref()
+    }
+
+    pub fn apply(&mut self, event: &TrackerEvent) {
+        self.state.apply(event);
+
+        match event {
+            TrackerEvent::GameDetected { .. } => {
+                self.status = PollStatus::LoadingGame;
+                self.selected_player = 0;
+                self.expanded = false;
+            }
+            TrackerEvent::ReportLoaded { .. } => self.status = PollStatus::InGame,
+            TrackerEvent::GameEnded { .. } => self.status = PollStatus::Waiting,
+            TrackerEvent::RankUpdated { .. } => {}
+            TrackerEvent::Polled {
+                in_game,
+                rate_limits,
+                cache,
+            } => {
+                self.last_poll = Some(Instant::now());
+                self.rate_limits = rate_limits.clone();
+                self.cache = *cache;
+
+                if !in_game {
+                    self.status = PollStatus::Waiting;
+                } else if self.status != PollStatus::LoadingGame {
+                    self.status = PollStatus::InGame;
+                }
+            }
+        }
+    }
+
+    pub fn tracker_stopped(&mut self, reason: String) {
+        self.status = PollStatus::Stopped(reason);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc => self.expanded = false,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                self.tab = self.tab.toggle()
+            }
+            KeyCode::Char('1') => self.tab = Tab::Game,
+            KeyCode::Char('2') => self.tab = Tab::History,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Enter | KeyCode::Char(' ') if self.tab == Tab::Game => {
+                self.expanded = !self.expanded
+            }
+            _ => {}
+        }
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let (selected, len) = match self.tab {
+            Tab::Game => (
+                &mut self.selected_player,
+                self.state
+                    .current_game
+                    .as_ref()
+                    .map_or(0, |r| r.players.len()),
+            ),
+            Tab::History => (&mut self.selected_history, self.state.history.len()),
+        };
+
+        if len > 0 {
+            *selected = selected.saturating_add_signed(offset).min(len - 1);
+        }
+    }
+}
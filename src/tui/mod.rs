@@ -0,0 +1,122 @@
+use std::future::Future;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::sync::{mpsc, watch};
+
+use league_notify::columns::Column;
+use league_notify::events::TrackerEvent;
+use league_notify::session::SessionSummary;
+use league_notify::Result;
+
+use self::app::App;
+
+mod app;
+mod ui;
+
+/// How long the input thread waits for a terminal event before checking if the UI has closed.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Interval at which the UI is redrawn without new data, to keep timers in the status bar ticking.
+const REDRAW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Restores the terminal when the UI stops, including when it is dropped early.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Leaves the full-screen UI, for when the process exits without dropping it.
+pub fn restore_terminal() {
+    ratatui::restore();
+}
+
+/// Runs the full-screen terminal UI alongside `tracker` until the user quits or `shutdown` is
+/// set, then asks the tracker to stop and waits for it to save the session.
+pub async fn run(
+    summoner_name: &str,
+    columns: &[Column],
+    verbose: bool,
+    mut events: broadcast::Receiver<TrackerEvent>,
+    tracker: impl Future<Output = Result<Option<SessionSummary>>>,
+    shutdown: &watch::Sender<bool>,
+) -> Result<Option<SessionSummary>> {
+    let mut terminal = ratatui::try_init()?;
+    let _guard = TerminalGuard;
+
+    let mut app = App::new(summoner_name, columns, verbose);
+    let mut input = spawn_input_reader();
+    let mut redraw = tokio::time::interval(REDRAW_INTERVAL);
+    let mut shutdown_requested = shutdown.subscribe();
+
+    tokio::pin!(tracker);
+    let mut tracker_running = true;
+
+    while !app.quit && !*shutdown_requested.borrow() {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+
+        tokio::select! {
+            result = &mut tracker, if tracker_running => {
+                tracker_running = false;
+
+                match result {
+                    // The tracker only stops with a summary once shutdown has been requested.
+                    Ok(Some(summary)) => return Ok(Some(summary)),
+                    Ok(None) => app.tracker_stopped("Could not get summoner".to_owned()),
+                    Err(e) => app.tracker_stopped(e.to_string()),
+                }
+            }
+            Some(event) = input.recv() => {
+                if let Event::Key(key) = event {
+                    app.handle_key(key);
+                }
+            }
+            event = events.recv() => match event {
+                Ok(event) => app.apply(&event),
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+            _ = shutdown_requested.changed() => {}
+            _ = redraw.tick() => {}
+        }
+    }
+
+    if !tracker_running {
+        return Ok(None);
+    }
+
+    shutdown.send_replace(true);
+
+    app.tracker_stopped("Saving session...".to_owned());
+    terminal.draw(|frame| ui::draw(frame, &app))?;
+
+    tracker.await
+}
+
+/// Reads terminal events on a blocking thread, which exits once the returned receiver is dropped.
+fn spawn_input_reader() -> mpsc::UnboundedReceiver<Event> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    tokio::task::spawn_blocking(move || {
+        while !sender.is_closed() {
+            match event::poll(INPUT_POLL_INTERVAL) {
+                Ok(true) => match event::read() {
+                    Ok(event) => {
+                        if sender.send(event).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                },
+                Ok(false) => {}
+                Err(_) => break,
+            }
+        }
+    });
+
+    receiver
+}
@@ -0,0 +1,329 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Cell, List, ListItem, ListState, Paragraph, Row, Table, TableState, Tabs, Wrap,
+};
+use ratatui::Frame;
+
+use league_notify::analysis::encounters;
+use league_notify::columns::Column;
+use league_notify::models::champion::ChampionWinRate;
+use league_notify::report::GameReport;
+use league_notify::util::{now_millis, StringExt};
+
+use super::app::{App, Tab};
+use crate::table::{self, PLAYER_COLOUR};
+
+const KEY_HELP: &str = "q quit · tab switch · ↑↓ select · enter details";
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [tabs_area, body_area, status_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let tabs = Tabs::new(Tab::TITLES)
+        .select(app.tab.index())
+        .highlight_style(
+            Style::new()
+                .fg(colour(PLAYER_COLOUR))
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::bordered().title(format!(" League Notifier - {} ", app.summoner_name)));
+
+    frame.render_widget(tabs, tabs_area);
+
+    match app.tab {
+        Tab::Game => draw_game(frame, app, body_area),
+        Tab::History => draw_history(frame, app, body_area),
+    }
+
+    draw_status(frame, app, status_area);
+}
+
+fn draw_game(frame: &mut Frame, app: &App, area: Rect) {
+    let report = match app.current_game() {
+        Some(report) => report,
+        None => {
+            let message = Paragraph::new(app.status.description()).block(Block::bordered());
+            frame.render_widget(message, area);
+            return;
+        }
+    };
+
+    let [summary_area, teams_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+
+    frame.render_widget(Line::from(report.summary()).bold(), summary_area);
+
+    let teams_area = if app.expanded {
+        let [teams_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(teams_area);
+
+        if let Some(player) = report.players.get(app.selected_player) {
+            draw_player_detail(frame, player, detail_area);
+        }
+
+        teams_area
+    } else {
+        teams_area
+    };
+
+    draw_teams(frame, app, report, Some(app.selected_player), teams_area);
+}
+
+/// Draws the blue and red team panels, highlighting the player at `selected` in
+/// `report.players`.
+fn draw_teams(
+    frame: &mut Frame,
+    app: &App,
+    report: &GameReport,
+    selected: Option<usize>,
+    area: Rect,
+) {
+    let [blue_area, red_area] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(area);
+
+    // Players are sorted by team, so each panel shows a contiguous slice of them.
+    let blue_count = report.players.iter().filter(|p| p.team_id == 100).count();
+
+    for (team_id, team_name, offset, team_area) in [
+        (100, "Blue", 0, blue_area),
+        (200, "Red", blue_count, red_area),
+    ] {
+        let players = report
+            .players
+            .iter()
+            .filter(|p| p.team_id as i64 == team_id)
+            .collect::<Vec<_>>();
+
+        let mut state = TableState::default().with_selected(
+            selected
+                .and_then(|s| s.checked_sub(offset))
+                .filter(|s| *s < players.len()),
+        );
+
+        let title = format!(
+            " {} Team - Bans: {} ",
+            team_name,
+            report.bans_string(team_id)
+        );
+
+        frame.render_stateful_widget(
+            team_table(app, &players).block(
+                Block::bordered()
+                    .title(title)
+                    .fg(colour(table::team_colour(team_id))),
+            ),
+            team_area,
+            &mut state,
+        );
+    }
+}
+
+/// Table of `players` with the columns chosen on the command line, coloured like the terminal
+/// table.
+fn team_table<'a>(app: &App, players: &[&'a ChampionWinRate]) -> Table<'a> {
+    let header = Row::new(app.columns.iter().map(|c| c.header())).bold();
+
+    let rows = players.iter().map(|p| {
+        Row::new(app.columns.iter().map(|c| {
+            Cell::new(c.value(p, app.verbose)).fg(colour(table::cell_colour(
+                *c,
+                p,
+                &app.summoner_name,
+            )))
+        }))
+    });
+
+    let widths = app.columns.iter().map(|c| match c {
+        Column::Champion | Column::Summoner | Column::Rank | Column::SmurfFactors => {
+            Constraint::Fill(2)
+        }
+        _ => Constraint::Fill(1),
+    });
+
+    Table::new(rows, widths)
+        .header(header.fg(Color::Reset))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+}
+
+/// Converts a colour of the terminal table's palette.
+fn colour(colour: comfy_table::Color) -> Color {
+    match colour {
+        comfy_table::Color::Rgb { r, g, b } => Color::Rgb(r, g, b),
+        comfy_table::Color::Red => Color::Red,
+        comfy_table::Color::Yellow => Color::Yellow,
+        _ => Color::Reset,
+    }
+}
+
+fn draw_player_detail(frame: &mut Frame, player: &ChampionWinRate, area: Rect) {
+    let mastery = match &player.mastery {
+        Some(mastery) => format!(
+            "Level {} - {} points",
+            mastery.champion_level, mastery.champion_points
+        ),
+        None => "Never played".to_owned(),
+    };
+
+    let role_history = player
+        .role
+        .distribution
+        .iter()
+        .map(|r| format!("{} {}", r.role.short_name(), r.games))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let runes = [player.keystone.as_deref(), player.secondary_tree.as_deref()]
+        .iter()
+        .flatten()
+        .copied()
+        .collect::<Vec<_>>();
+
+    let encounters = match encounters::encounters_string(&player.encounters) {
+        e if e.is_empty() => "Never".to_owned(),
+        e => e,
+    };
+
+    let field = |name: &'static str, value: String| {
+        Line::from(vec![
+            Span::from(format!("{}: ", name)).bold(),
+            Span::from(value),
+        ])
+    };
+
+    let mut lines = vec![
+        field(
+            "Summoner",
+            format!("{} (Level {})", player.summoner_name, player.summoner_level),
+        ),
+        field("Champion", player.champion_name.clone()),
+        field("Mastery", mastery),
+        field("Win Rate", player.win_rate_string()),
+        field("Rank", player.rank.clone()),
+        field("Role", player.role.role_string()),
+        field("Recent Roles", role_history),
+        field("Spells", player.spells.join(" / ")),
+        field("Runes", runes.join(" / ")),
+        field("Met Before", encounters),
+        field("Smurf Score", player.smurf.score.to_string()),
+    ];
+
+    lines.extend(
+        player
+            .smurf
+            .factors
+            .iter()
+            .map(|f| Line::from(format!("  {}", f))),
+    );
+
+    lines.push(Line::default());
+    lines.push(Line::from("Recent Matches").bold());
+
+    lines.extend(player.recent_matches.iter().map(|m| {
+        let (result, colour) = if m.won {
+            ("W", Color::Green)
+        } else {
+            ("L", Color::Red)
+        };
+
+        Line::from(vec![
+            Span::from(result).fg(colour).bold(),
+            Span::from(format!(
+                " {} {} - {}",
+                m.champion_name,
+                m.kda_string(),
+                time_ago(m.game_creation)
+            )),
+        ])
+    }));
+
+    let detail = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::bordered().title(format!(" {} ", player.summoner_name)));
+
+    frame.render_widget(detail, area);
+}
+
+fn draw_history(frame: &mut Frame, app: &App, area: Rect) {
+    if app.state.history.is_empty() {
+        let message = Paragraph::new("No games this session.").block(Block::bordered());
+        frame.render_widget(message, area);
+        return;
+    }
+
+    let [list_area, game_area] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(area);
+
+    let items = app
+        .state
+        .history
+        .iter()
+        .map(|h| ListItem::new(format!("{} - {}", time_ago(h.detected_at), h.summary)));
+
+    let mut state = ListState::default().with_selected(Some(app.selected_history));
+
+    frame.render_stateful_widget(
+        List::new(items)
+            .block(Block::bordered().title(" Games "))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+        list_area,
+        &mut state,
+    );
+
+    if let Some(entry) = app.state.history.get(app.selected_history) {
+        draw_teams(frame, app, &entry.report, None, game_area);
+    }
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let mut parts = vec![app.status.description()];
+
+    if let Some(last_poll) = app.last_poll {
+        parts.push(format!("Last poll {}s ago", last_poll.elapsed().as_secs()));
+    }
+
+    if !app.rate_limits.is_empty() {
+        let budget = app
+            .rate_limits
+            .iter()
+            .map(|l| format!("{}/{} per {}s", l.remaining, l.size, l.refill_seconds))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        parts.push(format!("Requests left {}", budget));
+        parts.push(format!("Cache hits {:.0}%", app.cache.hit_ratio() * 100.0));
+    }
+
+    if let Some(lp) = app.state.lp_history.last() {
+        parts.push(format!(
+            "{} {} {} LP",
+            lp.tier.clone().title_case(),
+            lp.rank,
+            lp.league_points
+        ));
+    }
+
+    parts.push(KEY_HELP.to_owned());
+
+    frame.render_widget(
+        Line::from(format!(" {}", parts.join(" │ "))).reversed(),
+        area,
+    );
+}
+
+fn time_ago(timestamp: i64) -> String {
+    let minutes = (now_millis() - timestamp).max(0) / 60_000;
+
+    match minutes {
+        0 => "just now".to_owned(),
+        1..=59 => format!("{}m ago", minutes),
+        60..=1439 => format!("{}h ago", minutes / 60),
+        _ => format!("{}d ago", minutes / 1440),
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 pub trait StringExt {
     fn title_case(self) -> String;
 }
@@ -7,3 +9,11 @@ impl StringExt for String {
         self[..1].to_uppercase() + &self[1..].to_lowercase()
     }
 }
+
+/// Current time as epoch milliseconds, the format timestamps in Riot API responses use.
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,15 @@
+use league_notify::api::redact_key;
+
+#[test]
+fn shows_only_end_of_key() {
+    assert_eq!(
+        redact_key("RGAPI-00000000-1111-2222-3333-444444444444"),
+        "***4444"
+    );
+}
+
+#[test]
+fn hides_short_keys_entirely() {
+    assert_eq!(redact_key("abcd"), "***");
+    assert_eq!(redact_key(""), "***");
+}
@@ -0,0 +1,70 @@
+use league_notify::analysis::roles::Role;
+use league_notify::columns::{sort_players, Column, SortKey};
+use league_notify::models::champion::ChampionWinRate;
+
+fn player(name: &str, team_id: u8, wins: u8, losses: u8, level: i64) -> ChampionWinRate {
+    let mut player = ChampionWinRate::new(1, "Annie".to_owned(), wins, losses);
+
+    player.summoner_name = name.to_owned();
+    player.team_id = team_id;
+    player.summoner_level = level;
+
+    player
+}
+
+fn names(players: &[ChampionWinRate]) -> Vec<&str> {
+    players.iter().map(|p| p.summoner_name.as_str()).collect()
+}
+
+#[test]
+fn parses_sort_keys() {
+    let key = "win-rate:desc".parse::<SortKey>().unwrap();
+    assert_eq!((key.column, key.descending), (Column::WinRate, true));
+
+    let key = "level".parse::<SortKey>().unwrap();
+    assert_eq!((key.column, key.descending), (Column::Level, false));
+
+    assert!("level:sideways".parse::<SortKey>().is_err());
+    assert!("height".parse::<SortKey>().is_err());
+}
+
+#[test]
+fn sorts_by_team_then_keys_in_turn() {
+    let mut players = vec![
+        player("d", 200, 9, 1, 30),
+        player("a", 100, 5, 5, 100),
+        player("b", 100, 9, 1, 50),
+        player("c", 100, 5, 5, 200),
+    ];
+
+    let keys = ["win-rate:desc", "level:asc"]
+        .into_iter()
+        .map(|k| k.parse().unwrap())
+        .collect::<Vec<SortKey>>();
+
+    sort_players(&mut players, &keys);
+
+    assert_eq!(names(&players), ["b", "a", "c", "d"]);
+}
+
+#[test]
+fn sorts_roles_in_lane_order() {
+    let mut players = [
+        (Role::Utility, "support"),
+        (Role::Middle, "mid"),
+        (Role::Top, "top"),
+        (Role::Bottom, "bot"),
+        (Role::Jungle, "jungle"),
+    ]
+    .into_iter()
+    .map(|(role, name)| {
+        let mut player = player(name, 100, 1, 1, 30);
+        player.role.predicted_role = Some(role);
+        player
+    })
+    .collect::<Vec<_>>();
+
+    sort_players(&mut players, &["role".parse().unwrap()]);
+
+    assert_eq!(names(&players), ["top", "jungle", "mid", "bot", "support"]);
+}
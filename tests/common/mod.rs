@@ -0,0 +1,26 @@
+#![allow(dead_code)]
+
+use league_notify::models::lol_match::{Info, MatchData, Metadata, Participant};
+
+/// Participant on `team_id` with `puuid`, every other field left empty.
+pub fn participant(puuid: &str, team_id: i64) -> Participant {
+    Participant {
+        puuid: puuid.to_owned(),
+        team_id,
+        ..Participant::default()
+    }
+}
+
+pub fn match_data(match_id: &str, game_creation: i64, participants: Vec<Participant>) -> MatchData {
+    MatchData {
+        metadata: Metadata {
+            match_id: match_id.to_owned(),
+            ..Metadata::default()
+        },
+        info: Info {
+            game_creation,
+            participants,
+            ..Info::default()
+        },
+    }
+}
@@ -0,0 +1,27 @@
+use league_notify::endpoints::ddragon::DDragonEndpoint;
+
+#[test]
+fn caches_versioned_images_by_version() {
+    let square = DDragonEndpoint::ChampionSquare("13.1.1", "Aatrox.png");
+
+    assert_eq!(
+        square.url(),
+        "https://ddragon.leagueoflegends.com/cdn/13.1.1/img/champion/Aatrox.png"
+    );
+    assert_eq!(
+        square.file_name().as_deref(),
+        Some("img/champion/Aatrox.png")
+    );
+    assert!(square.is_versioned());
+}
+
+#[test]
+fn caches_splash_art_outside_of_versions() {
+    let splash = DDragonEndpoint::ChampionSplash("Aatrox", 0);
+
+    assert_eq!(
+        splash.url(),
+        "https://ddragon.leagueoflegends.com/cdn/img/champion/splash/Aatrox_0.jpg"
+    );
+    assert!(!splash.is_versioned());
+}
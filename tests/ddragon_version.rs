@@ -0,0 +1,14 @@
+use league_notify::ddragon::updater::ddragon_version;
+
+#[test]
+fn converts_game_versions_to_patches() {
+    assert_eq!(ddragon_version("13.1.482.3244").as_deref(), Some("13.1.1"));
+    assert_eq!(ddragon_version("9.24.300").as_deref(), Some("9.24.1"));
+}
+
+#[test]
+fn rejects_malformed_game_versions() {
+    assert_eq!(ddragon_version(""), None);
+    assert_eq!(ddragon_version("13"), None);
+    assert_eq!(ddragon_version("latest.1"), None);
+}
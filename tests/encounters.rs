@@ -0,0 +1,87 @@
+use league_notify::analysis::encounters::{encounters_string, find_encounters};
+use league_notify::models::lol_match::Participant;
+
+mod common;
+
+use common::{match_data, participant};
+
+fn player(puuid: &str, team_id: i64, champion_name: &str, win: bool) -> Participant {
+    Participant {
+        champion_name: champion_name.to_owned(),
+        item0: 1001,
+        win,
+        ..participant(puuid, team_id)
+    }
+}
+
+#[test]
+fn finds_encounters_most_recent_first() {
+    let history = [
+        match_data(
+            "m1",
+            100,
+            vec![
+                player("me", 100, "Annie", true),
+                player("them", 100, "Ahri", true),
+            ],
+        ),
+        match_data(
+            "m2",
+            300,
+            vec![
+                player("me", 100, "Annie", false),
+                player("them", 200, "Zed", true),
+            ],
+        ),
+        match_data("m3", 200, vec![player("me", 100, "Annie", true)]),
+    ];
+
+    let encounters = find_encounters("me", "them", history.iter());
+
+    let match_ids = encounters
+        .iter()
+        .map(|e| e.match_id.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(match_ids, ["m2", "m1"]);
+    assert!(!encounters[0].same_team && encounters[1].same_team);
+    assert_eq!(encounters[0].item_ids, [1001]);
+}
+
+#[test]
+fn summarises_encounters_by_side() {
+    let history = [
+        match_data(
+            "m1",
+            100,
+            vec![
+                player("me", 100, "Annie", true),
+                player("them", 100, "Ahri", true),
+            ],
+        ),
+        match_data(
+            "m2",
+            300,
+            vec![
+                player("me", 100, "Annie", false),
+                player("them", 200, "Zed", true),
+            ],
+        ),
+        match_data(
+            "m3",
+            200,
+            vec![
+                player("me", 100, "Annie", true),
+                player("them", 200, "Zed", false),
+            ],
+        ),
+    ];
+
+    let encounters = find_encounters("me", "them", history.iter());
+
+    assert_eq!(
+        encounters_string(&encounters),
+        "With: 1W 0L, Against: 1W 1L (last as Zed)"
+    );
+    assert_eq!(encounters_string(&[]), "");
+}
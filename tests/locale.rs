@@ -0,0 +1,16 @@
+use league_notify::ddragon::updater::is_locale_format;
+
+#[test]
+fn accepts_language_and_country_codes() {
+    assert!(is_locale_format("en_US"));
+    assert!(is_locale_format("ko_KR"));
+}
+
+#[test]
+fn rejects_malformed_locales() {
+    assert!(!is_locale_format("../.."));
+    assert!(!is_locale_format("en"));
+    assert!(!is_locale_format("EN_us"));
+    assert!(!is_locale_format("en_US/"));
+    assert!(!is_locale_format("en-US"));
+}
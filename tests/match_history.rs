@@ -0,0 +1,20 @@
+use league_notify::models::lol_match::{MatchHistory, MAX_HISTORY_MATCHES};
+
+mod common;
+
+use common::match_data;
+
+#[test]
+fn drops_oldest_matches_beyond_limit() {
+    let mut history = MatchHistory::default();
+
+    // Inserted newest first, as refreshes return them.
+    for i in (0..MAX_HISTORY_MATCHES as i64 + 10).rev() {
+        history.insert(match_data(&format!("EUW1_{}", i), i, Vec::new()));
+    }
+
+    assert_eq!(history.len(), MAX_HISTORY_MATCHES);
+    assert!(!history.contains("EUW1_9"));
+    assert!(history.contains("EUW1_10"));
+    assert!(history.matches().all(|m| m.info.game_creation >= 10));
+}
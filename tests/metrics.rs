@@ -0,0 +1,34 @@
+#![cfg(feature = "metrics")]
+
+use std::time::Duration;
+
+use league_notify::metrics;
+
+#[test]
+fn renders_recorded_metrics_in_prometheus_format() {
+    let metrics = metrics::global();
+
+    metrics.record_request("summoner/v4/summoners/by-name", "200");
+    metrics.record_request("summoner/v4/summoners/by-name", "200");
+    metrics.record_rate_limited("method");
+    metrics.record_limiter_wait(Duration::from_millis(200));
+    metrics.record_limiter_wait(Duration::from_secs(2));
+    metrics.record_cache_hit();
+    metrics.record_cache_miss();
+
+    let rendered = metrics.render();
+
+    for line in [
+        "# TYPE league_notify_requests_total counter",
+        "league_notify_requests_total{endpoint=\"summoner/v4/summoners/by-name\",status=\"200\"} 2",
+        "league_notify_rate_limited_total{scope=\"method\"} 1",
+        "league_notify_limiter_wait_seconds_bucket{le=\"0.1\"} 0",
+        "league_notify_limiter_wait_seconds_bucket{le=\"0.25\"} 1",
+        "league_notify_limiter_wait_seconds_bucket{le=\"5\"} 2",
+        "league_notify_limiter_wait_seconds_bucket{le=\"+Inf\"} 2",
+        "league_notify_limiter_wait_seconds_sum 2.2",
+        "league_notify_cache_hit_ratio 0.5",
+    ] {
+        assert!(rendered.lines().any(|l| l == line), "missing {}", line);
+    }
+}
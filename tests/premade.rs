@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use league_notify::analysis::premade::{detect_premades, PremadePlayer};
+
+mod common;
+
+use common::{match_data, participant};
+
+fn players() -> Vec<PremadePlayer<'static>> {
+    [("a", 100), ("b", 100), ("c", 100), ("d", 200), ("e", 200)]
+        .into_iter()
+        .map(|(puuid, team_id)| PremadePlayer { puuid, team_id })
+        .collect()
+}
+
+#[test]
+fn groups_players_with_enough_shared_games() {
+    let together = |id| {
+        match_data(
+            id,
+            0,
+            vec![
+                participant("a", 100),
+                participant("b", 100),
+                participant("d", 200),
+            ],
+        )
+    };
+
+    // The same match listed for several players only counts once.
+    let recent_matches = HashMap::from([
+        ("a".to_owned(), vec![together("m1"), together("m2")]),
+        ("b".to_owned(), vec![together("m1")]),
+    ]);
+
+    let premades = detect_premades(&players(), &recent_matches);
+
+    assert_eq!(premades.get("a"), Some(&1));
+    assert_eq!(premades.get("b"), Some(&1));
+    assert_eq!(premades.len(), 2);
+}
+
+#[test]
+fn ignores_single_shared_games_and_opponents() {
+    let recent_matches = HashMap::from([(
+        "a".to_owned(),
+        vec![
+            match_data("m1", 0, vec![participant("a", 100), participant("c", 100)]),
+            // `a` and `d` are on different teams in the current game.
+            match_data("m2", 0, vec![participant("a", 100), participant("d", 100)]),
+            match_data("m3", 0, vec![participant("a", 100), participant("d", 100)]),
+        ],
+    )]);
+
+    assert!(detect_premades(&players(), &recent_matches).is_empty());
+}
+
+#[test]
+fn numbers_separate_groups() {
+    let recent_matches = HashMap::from([(
+        "a".to_owned(),
+        ["m1", "m2"]
+            .into_iter()
+            .map(|id| {
+                match_data(
+                    id,
+                    0,
+                    vec![
+                        participant("a", 100),
+                        participant("b", 100),
+                        participant("d", 200),
+                        participant("e", 200),
+                    ],
+                )
+            })
+            .collect(),
+    )]);
+
+    let premades = detect_premades(&players(), &recent_matches);
+
+    assert_eq!(premades.get("a"), premades.get("b"));
+    assert_eq!(premades.get("d"), premades.get("e"));
+    assert_ne!(premades.get("a"), premades.get("d"));
+    assert_eq!(premades.len(), 4);
+}
@@ -0,0 +1,185 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use league_notify::endpoints::CacheTtl;
+use league_notify::response_cache::{ResponseCache, MAX_PERSISTED_BYTES};
+use league_notify::MyError;
+
+#[tokio::test]
+async fn caches_until_ttl_expires() {
+    let cache = ResponseCache::default();
+    let ttl = CacheTtl::For(Duration::from_millis(50));
+
+    for _ in 0..2 {
+        let body = cache
+            .get_or_fetch("summoner", ttl, || async { Ok("a".to_owned()) })
+            .await;
+
+        assert_eq!(&*body.unwrap(), "a");
+    }
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+
+    let body = cache
+        .get_or_fetch("summoner", ttl, || async { Ok("b".to_owned()) })
+        .await;
+
+    assert_eq!(&*body.unwrap(), "b");
+
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses), (1, 2));
+}
+
+#[tokio::test]
+async fn never_caches_uncacheable_endpoints() {
+    let cache = ResponseCache::default();
+
+    for body in ["a", "b"] {
+        let res = cache
+            .get_or_fetch("spectator", CacheTtl::Never, || async move {
+                Ok(body.to_owned())
+            })
+            .await;
+
+        assert_eq!(&*res.unwrap(), body);
+    }
+
+    assert_eq!(cache.stats().hits, 0);
+}
+
+#[tokio::test]
+async fn does_not_cache_errors() {
+    let cache = ResponseCache::default();
+
+    let res = cache
+        .get_or_fetch("match", CacheTtl::Forever, || async {
+            Err(MyError::NotFound {
+                endpoint: "match".to_owned(),
+            })
+        })
+        .await;
+
+    assert!(res.is_err());
+
+    let res = cache
+        .get_or_fetch("match", CacheTtl::Forever, || async { Ok("a".to_owned()) })
+        .await;
+
+    assert_eq!(&*res.unwrap(), "a");
+}
+
+#[tokio::test]
+async fn coalesces_identical_requests_in_flight() {
+    let cache = ResponseCache::default();
+    let requests = AtomicUsize::new(0);
+
+    let fetch = || async {
+        requests.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        Ok("a".to_owned())
+    };
+
+    let (first, second) = tokio::join!(
+        cache.get_or_fetch("league", CacheTtl::Never, fetch),
+        cache.get_or_fetch("league", CacheTtl::Never, fetch),
+    );
+
+    assert_eq!(&*first.unwrap(), "a");
+    assert_eq!(&*second.unwrap(), "a");
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+    assert_eq!(cache.stats().coalesced, 1);
+}
+
+#[tokio::test]
+async fn saves_and_loads_unexpired_entries() {
+    let path =
+        std::env::temp_dir().join(format!("league_notify-cache-{}.json", std::process::id()));
+
+    let cache = ResponseCache::default();
+
+    for (key, ttl) in [
+        ("match", CacheTtl::Forever),
+        ("league", CacheTtl::For(Duration::from_secs(60))),
+        ("summoner", CacheTtl::For(Duration::from_millis(10))),
+    ] {
+        let _ = cache
+            .get_or_fetch(key, ttl, || async { Ok("a".to_owned()) })
+            .await;
+    }
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    cache.save(&path).unwrap();
+
+    let loaded = ResponseCache::default();
+    loaded.load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    for (key, cached) in [("match", true), ("league", true), ("summoner", false)] {
+        let body = loaded
+            .get_or_fetch(key, CacheTtl::Never, || async { Ok("b".to_owned()) })
+            .await;
+
+        assert_eq!(&*body.unwrap(), if cached { "a" } else { "b" });
+    }
+}
+
+#[tokio::test]
+async fn saves_only_most_recent_entries_within_size_limit() {
+    let path = std::env::temp_dir().join(format!(
+        "league_notify-cache-limit-{}.json",
+        std::process::id()
+    ));
+
+    let cache = ResponseCache::default();
+    let body = "a".repeat(MAX_PERSISTED_BYTES / 4);
+
+    for i in 0..6 {
+        let body = body.clone();
+
+        let _ = cache
+            .get_or_fetch(&format!("match-{}", i), CacheTtl::Forever, || async {
+                Ok(body)
+            })
+            .await;
+    }
+
+    cache.save(&path).unwrap();
+
+    let loaded = ResponseCache::default();
+    loaded.load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    for i in 0..6 {
+        let res = loaded
+            .get_or_fetch(&format!("match-{}", i), CacheTtl::Never, || async {
+                Ok("b".to_owned())
+            })
+            .await;
+
+        assert_eq!(&*res.unwrap() == "b", i < 2, "match-{}", i);
+    }
+}
+
+#[tokio::test]
+async fn refresh_bypasses_and_replaces_cached_response() {
+    let cache = ResponseCache::default();
+    let ttl = CacheTtl::For(Duration::from_secs(300));
+
+    cache
+        .get_or_fetch("leagues", ttl, || async { Ok("old".to_owned()) })
+        .await
+        .unwrap();
+
+    let body = cache
+        .refresh("leagues", ttl, || async { Ok("new".to_owned()) })
+        .await;
+
+    assert_eq!(&*body.unwrap(), "new");
+
+    let body = cache
+        .get_or_fetch("leagues", ttl, || async { Ok("unused".to_owned()) })
+        .await;
+
+    assert_eq!(&*body.unwrap(), "new");
+}
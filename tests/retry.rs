@@ -0,0 +1,198 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Response, Server, StatusCode};
+use reqwest::Client;
+
+use league_notify::retry::{self, RetryPolicy};
+
+/// Serves `responses` in order, repeating the last one, and counts the requests received.
+fn mock_server(responses: Vec<(StatusCode, Option<&'static str>)>) -> (String, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&requests);
+    let responses = Arc::new(responses);
+
+    let make_service = make_service_fn(move |_| {
+        let counter = Arc::clone(&counter);
+        let responses = Arc::clone(&responses);
+
+        async move {
+            Ok::<_, Infallible>(service_fn(move |_req| {
+                let index = counter.fetch_add(1, Ordering::SeqCst);
+                let (status, retry_after) = responses[index.min(responses.len() - 1)];
+
+                let mut response = Response::builder().status(status);
+
+                if let Some(retry_after) = retry_after {
+                    response = response.header("retry-after", retry_after);
+                }
+
+                async move { Ok::<_, Infallible>(response.body(Body::from("ok")).unwrap()) }
+            }))
+        }
+    });
+
+    let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+    let url = format!("http://{}/", server.local_addr());
+
+    tokio::spawn(server);
+
+    (url, requests)
+}
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy {
+        initial_backoff: Duration::from_millis(10),
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    }
+}
+
+#[tokio::test]
+async fn retries_server_errors_until_success() {
+    let (url, requests) = mock_server(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, None),
+        (StatusCode::INTERNAL_SERVER_ERROR, None),
+        (StatusCode::OK, None),
+    ]);
+
+    let res = retry::get(&Client::new(), &fast_policy(), &url).await;
+
+    assert_eq!(res.unwrap().text().await.unwrap(), "ok");
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let (url, requests) = mock_server(vec![(StatusCode::BAD_GATEWAY, None)]);
+
+    let res = retry::get(&Client::new(), &fast_policy(), &url).await;
+
+    assert!(res.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let (url, requests) = mock_server(vec![(StatusCode::NOT_FOUND, None), (StatusCode::OK, None)]);
+
+    let res = retry::get(&Client::new(), &fast_policy(), &url).await;
+
+    assert!(res.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn only_retries_configured_statuses() {
+    let (url, requests) = mock_server(vec![
+        (StatusCode::SERVICE_UNAVAILABLE, None),
+        (StatusCode::OK, None),
+    ]);
+
+    let policy = RetryPolicy {
+        retryable_statuses: vec![StatusCode::TOO_MANY_REQUESTS],
+        ..fast_policy()
+    };
+
+    let res = retry::get(&Client::new(), &policy, &url).await;
+
+    assert!(res.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn waits_for_retry_after() {
+    let (url, requests) = mock_server(vec![
+        (StatusCode::TOO_MANY_REQUESTS, Some("1")),
+        (StatusCode::OK, None),
+    ]);
+
+    let started = Instant::now();
+    let res = retry::get(&Client::new(), &fast_policy(), &url).await;
+
+    assert!(res.is_ok());
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn ignores_malformed_retry_after() {
+    let (url, requests) = mock_server(vec![
+        (StatusCode::TOO_MANY_REQUESTS, Some("soon")),
+        (StatusCode::OK, None),
+    ]);
+
+    let res = retry::get(&Client::new(), &fast_policy(), &url).await;
+
+    assert!(res.is_ok());
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn stops_at_deadline() {
+    let (url, requests) = mock_server(vec![(StatusCode::SERVICE_UNAVAILABLE, None)]);
+
+    let policy = RetryPolicy {
+        max_attempts: 10,
+        initial_backoff: Duration::from_millis(100),
+        deadline: Some(Duration::from_millis(50)),
+        ..fast_policy()
+    };
+
+    let res = retry::get(&Client::new(), &policy, &url).await;
+
+    assert!(res.is_err());
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn retries_connection_errors() {
+    // Bind and immediately release a port so that nothing is listening on it.
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+
+    let started = Instant::now();
+    let res = retry::get(&Client::new(), &fast_policy(), &format!("http://{}/", addr)).await;
+
+    assert!(res.is_err());
+    // Backoffs of 10ms and 20ms before the second and third attempts.
+    assert!(started.elapsed() >= Duration::from_millis(30));
+}
+
+#[test]
+fn backoff_grows_exponentially_up_to_max() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        backoff_multiplier: 2.0,
+        max_backoff: Duration::from_millis(1000),
+        ..RetryPolicy::default()
+    };
+
+    let backoffs = (1..=5)
+        .map(|attempt| policy.backoff(attempt).as_millis())
+        .collect::<Vec<_>>();
+
+    assert_eq!(backoffs, vec![100, 200, 400, 800, 1000]);
+}
+
+#[test]
+fn jitter_stays_within_bounds() {
+    let policy = RetryPolicy {
+        max_attempts: 2,
+        initial_backoff: Duration::from_millis(100),
+        jitter: 0.5,
+        ..RetryPolicy::default()
+    };
+
+    for _ in 0..100 {
+        let delay = policy.start().next_delay(None).unwrap();
+
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(150));
+    }
+}
@@ -0,0 +1,104 @@
+use league_notify::analysis::roles::{predict_role, role_distribution, Role};
+use league_notify::models::ddragon_champions::Champion;
+use league_notify::models::lol_match::{MatchData, Participant};
+
+mod common;
+
+use common::{match_data, participant};
+
+const HEAL: i64 = 7;
+const FLASH: i64 = 4;
+const TELEPORT: i64 = 12;
+
+fn game(match_id: &str, champion_id: i64, team_position: &str) -> MatchData {
+    match_data(
+        match_id,
+        0,
+        vec![Participant {
+            champion_id,
+            team_position: team_position.to_owned(),
+            ..participant("player", 100)
+        }],
+    )
+}
+
+fn champion(key: i64, tags: &[&str]) -> Champion {
+    Champion {
+        key,
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        ..Champion::default()
+    }
+}
+
+#[test]
+fn breaks_distribution_ties_in_role_order() {
+    let matches = [
+        game("EUW1_1", 1, "UTILITY"),
+        game("EUW1_2", 1, "MIDDLE"),
+        game("EUW1_3", 1, "TOP"),
+        game("EUW1_4", 1, "UTILITY"),
+        game("EUW1_5", 1, "MIDDLE"),
+    ];
+
+    let roles = role_distribution("player", &matches)
+        .iter()
+        .map(|r| (r.role, r.games))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        roles,
+        [(Role::Middle, 2), (Role::Utility, 2), (Role::Top, 1)]
+    );
+}
+
+#[test]
+fn predicts_most_common_role_on_champion_with_ties_in_role_order() {
+    let matches = [
+        game("EUW1_1", 1, "BOTTOM"),
+        game("EUW1_2", 1, "MIDDLE"),
+        game("EUW1_3", 2, "TOP"),
+    ];
+
+    for _ in 0..10 {
+        assert_eq!(
+            predict_role(
+                "player",
+                Some(&champion(1, &["Mage"])),
+                (FLASH, HEAL),
+                &matches
+            ),
+            Some(Role::Middle)
+        );
+    }
+}
+
+#[test]
+fn spells_take_precedence_over_primary_class() {
+    assert_eq!(
+        predict_role(
+            "player",
+            Some(&champion(1, &["Mage", "Marksman"])),
+            (FLASH, HEAL),
+            &[]
+        ),
+        Some(Role::Bottom)
+    );
+    assert_eq!(
+        predict_role(
+            "player",
+            Some(&champion(1, &["Marksman", "Fighter"])),
+            (FLASH, TELEPORT),
+            &[]
+        ),
+        Some(Role::Top)
+    );
+    assert_eq!(
+        predict_role(
+            "player",
+            Some(&champion(1, &["Marksman"])),
+            (FLASH, TELEPORT),
+            &[]
+        ),
+        Some(Role::Bottom)
+    );
+}
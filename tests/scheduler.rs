@@ -0,0 +1,83 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use league_notify::api::{Limiter, Scheduler};
+use league_notify::Priority;
+
+/// Spawns a request of `priority` that records its priority in `order` once it gets budget.
+fn request(
+    scheduler: &Arc<Scheduler>,
+    order: &Arc<Mutex<Vec<Priority>>>,
+    priority: Priority,
+) -> tokio::task::JoinHandle<()> {
+    let scheduler = Arc::clone(scheduler);
+    let order = Arc::clone(order);
+
+    tokio::spawn(async move {
+        scheduler.wait_for_budget(priority).await;
+        order.lock().unwrap().push(priority);
+    })
+}
+
+#[tokio::test]
+async fn live_game_requests_go_ahead_of_queued_background_requests() {
+    let scheduler = Arc::new(Scheduler::new(vec![Limiter::new(10, 3600)]));
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    scheduler.limiters()[0].empty();
+
+    let mut requests = (0..3)
+        .map(|_| request(&scheduler, &order, Priority::Background))
+        .collect::<Vec<_>>();
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    requests.push(request(&scheduler, &order, Priority::LiveGame));
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    assert!(order.lock().unwrap().is_empty());
+
+    scheduler.limiters()[0].refill();
+
+    for request in requests {
+        request.await.unwrap();
+    }
+
+    assert_eq!(
+        *order.lock().unwrap(),
+        [
+            Priority::LiveGame,
+            Priority::Background,
+            Priority::Background,
+            Priority::Background
+        ]
+    );
+}
+
+#[tokio::test]
+async fn cancelled_requests_stop_holding_back_lower_priorities() {
+    let scheduler = Arc::new(Scheduler::new(vec![Limiter::new(10, 3600)]));
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    scheduler.limiters()[0].empty();
+
+    let live_game = request(&scheduler, &order, Priority::LiveGame);
+
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    live_game.abort();
+    let _ = live_game.await;
+
+    scheduler.limiters()[0].refill();
+
+    tokio::time::timeout(
+        Duration::from_secs(1),
+        request(&scheduler, &order, Priority::Background),
+    )
+    .await
+    .expect("Background request still waiting on a cancelled request")
+    .unwrap();
+
+    assert_eq!(*order.lock().unwrap(), [Priority::Background]);
+}
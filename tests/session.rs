@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use league_notify::report::GameReport;
+use league_notify::session::SessionState;
+use league_notify::util::now_millis;
+
+fn report(game_id: u64) -> Arc<GameReport> {
+    Arc::new(GameReport {
+        game_id,
+        queue_id: 420,
+        queue_name: None,
+        map_id: 11,
+        map_name: None,
+        game_mode: "CLASSIC".to_owned(),
+        game_start_time: 0,
+        bans: Vec::new(),
+        players: Vec::new(),
+    })
+}
+
+#[test]
+fn keeps_notified_games_and_last_report_between_runs() {
+    let path =
+        std::env::temp_dir().join(format!("league_notify-session-{}.json", std::process::id()));
+
+    let mut state = SessionState::default();
+    state.mark_notified(report(1));
+    state.mark_notified(report(2));
+    state.save(&path).unwrap();
+
+    let loaded = SessionState::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(loaded.is_notified(1) && loaded.is_notified(2));
+    assert!(loaded.report(1).is_none());
+    assert_eq!(loaded.report(2).map(|r| r.game_id), Some(2));
+}
+
+#[test]
+fn forgets_games_notified_long_ago() {
+    let path =
+        std::env::temp_dir().join(format!("league_notify-pruned-{}.json", std::process::id()));
+
+    let mut state = SessionState::default();
+    state.mark_notified(report(1));
+    state
+        .notified_games
+        .insert(1, now_millis() - 2 * 24 * 60 * 60 * 1000);
+    state.save(&path).unwrap();
+
+    let loaded = SessionState::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!loaded.is_notified(1));
+    assert!(loaded.last_report.is_none());
+}
@@ -0,0 +1,87 @@
+use league_notify::analysis::smurf::{SmurfConfig, SmurfScore};
+use league_notify::models::leagues::LeagueRank;
+use league_notify::models::lol_match::Participant;
+
+mod common;
+
+use common::{match_data, participant};
+
+fn rank(tier: &str, wins: i64, losses: i64) -> LeagueRank {
+    LeagueRank {
+        tier: tier.to_owned(),
+        wins,
+        losses,
+        ..LeagueRank::default()
+    }
+}
+
+#[test]
+fn sums_weights_of_matching_factors() {
+    let config = SmurfConfig::default();
+    let matches = [match_data(
+        "EUW1_1",
+        0,
+        vec![Participant {
+            kills: 10,
+            deaths: 1,
+            assists: 5,
+            ..participant("player", 100)
+        }],
+    )];
+
+    let score = SmurfScore::new(
+        &config,
+        30,
+        Some(&rank("DIAMOND", 18, 2)),
+        "player",
+        &matches,
+    );
+
+    // Every factor matches, the sum is capped at 100.
+    assert_eq!(score.score, 100);
+    assert!(score.flagged);
+    assert_eq!(score.factors.len(), 5);
+}
+
+#[test]
+fn ignores_unknown_tiers() {
+    let config = SmurfConfig::default();
+
+    let score = SmurfScore::new(&config, 30, Some(&rank("UNRANKED", 5, 10)), "player", &[]);
+
+    assert_eq!(
+        score.score,
+        config.low_level_weight + config.few_games_weight
+    );
+    assert!(!score.flagged);
+}
+
+#[test]
+fn uses_configured_weights() {
+    let config = SmurfConfig {
+        threshold: 10,
+        low_level_weight: 10,
+        ..SmurfConfig::default()
+    };
+
+    let score = SmurfScore::new(&config, 30, None, "player", &[]);
+
+    assert_eq!(score.score, 10);
+    assert!(score.flagged);
+}
+
+#[test]
+fn rejects_invalid_config() {
+    let threshold = SmurfConfig {
+        threshold: 101,
+        ..SmurfConfig::default()
+    };
+    let tier = SmurfConfig {
+        high_tier: "WOOD".to_owned(),
+        ..SmurfConfig::default()
+    };
+
+    assert!(threshold.validate().is_err());
+    assert!(tier.validate().is_err());
+    assert!(SmurfConfig::default().validate().is_ok());
+}
@@ -0,0 +1,42 @@
+use std::io::Cursor;
+
+use image::{GenericImageView, ImageFormat, Rgba, RgbaImage};
+use league_notify::ddragon::sprite::crop_sprite;
+use league_notify::models::ddragon_champions::Image;
+
+#[test]
+fn crops_image_out_of_sprite_sheet() {
+    // 4x2 sheet with a red 2x2 image in its right half.
+    let sheet = RgbaImage::from_fn(4, 2, |x, _| {
+        if x >= 2 {
+            Rgba([255, 0, 0, 255])
+        } else {
+            Rgba([0, 0, 255, 255])
+        }
+    });
+
+    let mut png = Vec::new();
+    sheet
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .unwrap();
+
+    let image = Image {
+        x: 2,
+        y: 0,
+        w: 2,
+        h: 2,
+        ..Image::default()
+    };
+
+    let cropped = image::load_from_memory(&crop_sprite(&png, &image).unwrap()).unwrap();
+
+    assert_eq!(cropped.dimensions(), (2, 2));
+    assert!(cropped
+        .pixels()
+        .all(|(_, _, p)| p == Rgba([255, 0, 0, 255])));
+}
+
+#[test]
+fn rejects_invalid_sprite_sheets() {
+    assert!(crop_sprite(b"not a png", &Image::default()).is_err());
+}